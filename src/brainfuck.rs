@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::mem;
+use std::slice;
 
 
 #[derive(PartialEq, Clone, Debug)]
@@ -8,86 +11,500 @@ pub enum Op {
     DecVal(u8),
     Print,
     Read,
-    While { ops: Vec<Op> },
+    While { ops: Vec<Node> },
     SetRegisterToZero,
+    /// Only at program start: the tape begins with `data` and the pointer
+    /// is at `ptr`.
+    InitTape { data: Vec<u8>, ptr: usize },
+    PrintBytes(Vec<u8>),
 }
 
-pub fn compact(ast: &[Op]) -> Vec<Op> {
+/// Range of source characters (`start..end`) an op was compiled from.
+pub type Span = (usize, usize);
+
+#[derive(PartialEq, Debug)]
+pub struct Node {
+    pub op: Op,
+    pub span: Span,
+}
+
+impl Node {
+    pub fn new(op: Op, span: Span) -> Node {
+        Node { op, span }
+    }
+}
+
+// Programs can nest loops deeper than the stack could hold a frame per
+// loop, so copying and dropping the tree walk it without recursing.
+impl Clone for Node {
+    fn clone(&self) -> Node {
+        match self.op {
+            Op::While { ref ops } => Node::new(Op::While { ops: map_levels(ops, |ops| ops) }, self.span),
+            ref op => Node::new(op.clone(), self.span),
+        }
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        if let Op::While { ref mut ops } = self.op {
+            let mut nodes = mem::take(ops);
+            while let Some(mut node) = nodes.pop() {
+                if let Op::While { ref mut ops } = node.op {
+                    nodes.append(ops);
+                }
+            }
+        }
+    }
+}
+
+// A list of ops being rebuilt: the ops still to visit, the new list and
+// the span of the loop whose body it is.
+struct Level<'a, T> {
+    ops: slice::Iter<'a, Node>,
+    result: Vec<Node>,
+    span: Span,
+    state: T,
+}
+
+/// Rebuilds the tree from the innermost loops outwards without recursing:
+/// `level` gets every list of ops once the loops in it are rebuilt and
+/// returns what replaces it.
+pub fn map_levels<F: FnMut(Vec<Node>) -> Vec<Node>>(ast: &[Node], mut level: F) -> Vec<Node> {
+    let mut stack = vec![Level { ops: ast.iter(), result: Vec::new(), span: (0, 0), state: () }];
+    loop {
+        let top = stack.last_mut().unwrap();
+        match top.ops.next() {
+            Some(node) => match node.op {
+                Op::While { ref ops } => {
+                    stack.push(Level { ops: ops.iter(), result: Vec::new(), span: node.span, state: () })
+                }
+                _ => top.result.push(node.clone()),
+            },
+            None => {
+                let done = stack.pop().unwrap();
+                let ops = level(done.result);
+                match stack.last_mut() {
+                    Some(parent) => parent.result.push(Node::new(Op::While { ops }, done.span)),
+                    None => return ops,
+                }
+            }
+        }
+    }
+}
+
+pub const HEAP_SIZE: usize = 4092;
+
+fn merge(a: Span, b: Span) -> Span {
+    (a.0.min(b.0), a.1.max(b.1))
+}
+
+pub fn merge_spans(ast: &[Node]) -> Option<Span> {
+    ast.iter().map(|n| n.span).fold(None, |acc, s| match acc {
+        Some(a) => Some(merge(a, s)),
+        None => Some(s),
+    })
+}
+
+fn push_repeated(compacted_ast: &mut Vec<Node>, op: Op, span: Span, count: usize) {
+    match op {
+        Op::IncPointer(n) => compacted_ast.push(Node::new(Op::IncPointer(n + count), span)),
+        Op::DecPointer(n) => compacted_ast.push(Node::new(Op::DecPointer(n + count), span)),
+        Op::IncVal(n) => compacted_ast.push(Node::new(Op::IncVal(n.wrapping_add(count as u8)), span)),
+        Op::DecVal(n) => compacted_ast.push(Node::new(Op::DecVal(n.wrapping_add(count as u8)), span)),
+        _ => (),
+    }
+}
+
+// Folds the runs of one list whose loops are already compacted.
+fn compact_level(ast: Vec<Node>) -> Vec<Node> {
     let mut compacted_ast = Vec::new();
-    let mut current_op: Option<Op> = None;
+    let mut current_op: Option<(Op, Span)> = None;
     let mut count = 0;
 
-    for op in ast {
-        if let Some(curr_op) = current_op.clone() {
-            if *op == curr_op {
+    for node in ast {
+        if let Some((curr_op, span)) = current_op.clone() {
+            if node.op == curr_op {
                 count += 1;
+                current_op = Some((curr_op, merge(span, node.span)));
             } else {
-                match curr_op {
-                    Op::IncPointer(n) => compacted_ast.push(Op::IncPointer(n + count)),
-                    Op::DecPointer(n) => compacted_ast.push(Op::DecPointer(n + count)),
-                    Op::IncVal(n) => compacted_ast.push(Op::IncVal(n + count as u8)),
-                    Op::DecVal(n) => compacted_ast.push(Op::DecVal(n + count as u8)),
-                    _ => (),
-                }
+                push_repeated(&mut compacted_ast, curr_op, span, count);
                 current_op = None;
                 count = 0;
             }
         }
-        match *op {
+        match node.op {
             Op::While { ref ops } => {
-                let compacted_ops = compact(ops);
-                if compacted_ops == [Op::IncVal(1)] || compacted_ops == [Op::DecVal(1)] {
-                    compacted_ast.push(Op::SetRegisterToZero)
+                let ops_only: Vec<&Op> = ops.iter().map(|n| &n.op).collect();
+                if ops_only == [&Op::IncVal(1)] || ops_only == [&Op::DecVal(1)] {
+                    compacted_ast.push(Node::new(Op::SetRegisterToZero, node.span))
                 } else {
-                    compacted_ast.push(Op::While { ops: compacted_ops })
+                    compacted_ast.push(node)
+                }
+            }
+            Op::IncPointer(_) | Op::DecPointer(_) | Op::IncVal(_) | Op::DecVal(_) => {
+                if current_op.is_none() {
+                    current_op = Some((node.op.clone(), node.span));
                 }
             }
-            Op::Print => compacted_ast.push(Op::Print),
-            Op::Read => compacted_ast.push(Op::Read),
-            _ => current_op = Some(op.clone()),
+            _ => compacted_ast.push(node),
         }
     }
 
-    if let Some(curr_op) = current_op.clone() {
-        match curr_op {
-            Op::IncPointer(n) => compacted_ast.push(Op::IncPointer(n + count)),
-            Op::DecPointer(n) => compacted_ast.push(Op::DecPointer(n + count)),
-            Op::IncVal(n) => compacted_ast.push(Op::IncVal(n + count as u8)),
-            Op::DecVal(n) => compacted_ast.push(Op::DecVal(n + count as u8)),
-            _ => (),
-        }
+    if let Some((curr_op, span)) = current_op {
+        push_repeated(&mut compacted_ast, curr_op, span, count);
     }
 
     compacted_ast
 }
 
-pub fn get_ast(code: &[char]) -> (Vec<Op>, usize) {
+pub fn compact(ast: &[Node]) -> Vec<Node> {
+    map_levels(ast, compact_level)
+}
+
+// Stops at an unmatched `]`, returning the ops before it and its position.
+fn parse(code: &[char]) -> (Vec<Node>, usize) {
+    // the lists around the loops still open, with where each loop starts
+    let mut open: Vec<(Vec<Node>, usize)> = Vec::new();
     let mut ops = Vec::new();
+    for (i, &ch) in code.iter().enumerate() {
+        let op = match ch {
+            '>' => Op::IncPointer(1),
+            '<' => Op::DecPointer(1),
+            '+' => Op::IncVal(1),
+            '-' => Op::DecVal(1),
+            '.' => Op::Print,
+            ',' => Op::Read,
+            '[' => {
+                open.push((mem::take(&mut ops), i));
+                continue;
+            }
+            ']' => match open.pop() {
+                Some((outer, start)) => {
+                    let body = mem::replace(&mut ops, outer);
+                    ops.push(Node::new(Op::While { ops: body }, (start, i + 1)));
+                    continue;
+                }
+                None => return (ops, i),
+            },
+            _ => continue,
+        };
+        ops.push(Node::new(op, (i, i + 1)));
+    }
+    if let Some((_, start)) = open.last() {
+        panic!("while loop at {} needs to end with ']'", start);
+    }
+    (ops, code.len())
+}
+
+/// Deepest loop nesting the tree interpreter, the JIT and the compilers
+/// accept. They recurse once per loop, so this bounds their stack use, also
+/// within the 1MB stack of a wasm build. The parser, the passes and the
+/// bytecode VM handle any depth.
+pub const MAX_NESTING: usize = 1000;
+
+/// Fails for programs the recursive engines and backends can't take.
+pub fn check_nesting(code: &[char]) -> Result<(), String> {
+    let mut depth = 0;
+    for (i, &ch) in code.iter().enumerate() {
+        match ch {
+            '[' if depth == MAX_NESTING => {
+                return Err(format!("loops nested more than {} deep at {}", MAX_NESTING, i))
+            }
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// `get_ast` needs balanced brackets.
+pub fn check_brackets(code: &[char]) -> Result<(), String> {
+    let mut open = vec![];
+    for (i, &ch) in code.iter().enumerate() {
+        match ch {
+            '[' => open.push(i),
+            ']' if open.pop().is_none() => return Err(format!("unmatched ']' at {}", i)),
+            _ => (),
+        }
+    }
+    match open.pop() {
+        Some(i) => Err(format!("unmatched '[' at {}", i)),
+        None => Ok(()),
+    }
+}
+
+/// The program's commands without the comments, one line per run of
+/// commands and one indented block per loop that contains other loops.
+pub fn format(code: &[char]) -> Result<String, String> {
+    check_brackets(code)?;
+    let mut out = String::new();
+    let mut line = String::new();
+    let mut depth = 0;
     let mut i = 0;
     while i < code.len() {
-        let ch = code[i];
-        let op = match ch {
-            '>' => Some(Op::IncPointer(1)),
-            '<' => Some(Op::DecPointer(1)),
-            '+' => Some(Op::IncVal(1)),
-            '-' => Some(Op::DecVal(1)),
-            '.' => Some(Op::Print),
-            ',' => Some(Op::Read),
+        match code[i] {
             '[' => {
-                let (ops, size) = get_ast(&code[(i + 1..code.len())]);
-                i += size + 1;
-                match code[i] {
-                    ']' => Some(Op::While { ops: ops }),
-                    x => panic!("while loop needs to end with ']' but was with '{:?}'", x),
+                let end = matching_bracket(code, i);
+                if code[i + 1..end].contains(&'[') {
+                    push_line(&mut out, &mut line, depth);
+                    line.push('[');
+                    push_line(&mut out, &mut line, depth);
+                    depth += 1;
+                } else {
+                    line.extend(code[i..end + 1].iter().filter(|&&c| is_command(c)));
+                    i = end;
                 }
             }
-            ']' => return (ops, i),  
-            _ => None,
-        };
-        if let Some(op) = op {
-            ops.push(op);
+            ']' => {
+                push_line(&mut out, &mut line, depth);
+                depth -= 1;
+                line.push(']');
+                push_line(&mut out, &mut line, depth);
+            }
+            c if is_command(c) => line.push(c),
+            _ => (),
         }
         i += 1;
     }
-    (ops, i)
+    push_line(&mut out, &mut line, depth);
+    Ok(out)
+}
+
+// `#` is kept for the debugger.
+fn is_command(c: char) -> bool {
+    "+-<>.,[]#".contains(c)
+}
+
+fn matching_bracket(code: &[char], open: usize) -> usize {
+    let mut depth = 0;
+    for (i, &c) in code.iter().enumerate().skip(open) {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 1 => return i,
+            ']' => depth -= 1,
+            _ => (),
+        }
+    }
+    unreachable!("the brackets are checked")
+}
+
+fn push_line(out: &mut String, line: &mut String, depth: usize) {
+    if !line.is_empty() {
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(line);
+        out.push('\n');
+        line.clear();
+    }
+}
+
+pub fn get_ast(code: &[char]) -> (Vec<Node>, usize) {
+    parse(code)
+}
+
+/// 1-based line and column of a source position.
+pub fn line_col(source: &[char], pos: usize) -> (usize, usize) {
+    let before = &source[..pos.min(source.len())];
+    let line = before.iter().filter(|&&c| c == '\n').count() + 1;
+    let col = before.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+    (line, col)
+}
+
+// What is statically known about the tape: cells are keyed by their offset
+// from the position the pointer had when tracking started, `None` marks a
+// cell whose value is unknown.
+#[derive(Clone)]
+struct KnownTape {
+    pos: isize,
+    cells: HashMap<isize, Option<u8>>,
+    rest_zero: bool,
+    // the lowest and highest offsets visited
+    lowest: isize,
+    highest: isize,
+}
+
+impl KnownTape {
+    fn at_start() -> KnownTape {
+        KnownTape {
+            pos: 0,
+            cells: HashMap::new(),
+            rest_zero: true,
+            lowest: 0,
+            highest: 0,
+        }
+    }
+
+    fn after_loop() -> KnownTape {
+        let mut cells = HashMap::new();
+        cells.insert(0, Some(0));
+        KnownTape {
+            pos: 0,
+            cells,
+            rest_zero: false,
+            lowest: 0,
+            highest: 0,
+        }
+    }
+
+    fn unknown() -> KnownTape {
+        KnownTape {
+            pos: 0,
+            cells: HashMap::new(),
+            rest_zero: false,
+            lowest: 0,
+            highest: 0,
+        }
+    }
+
+    fn get(&self) -> Option<u8> {
+        match self.cells.get(&self.pos) {
+            Some(v) => *v,
+            None if self.rest_zero => Some(0),
+            None => None,
+        }
+    }
+
+    fn set(&mut self, val: Option<u8>) {
+        self.cells.insert(self.pos, val);
+    }
+
+    fn move_by(&mut self, n: isize) {
+        self.pos += n;
+        self.lowest = self.lowest.min(self.pos);
+        self.highest = self.highest.max(self.pos);
+        // Past either end the pointer wraps around, so with offsets from
+        // the start of the tape the untouched cells are no longer the
+        // zero-initialised ones, and with offsets from an unknown position
+        // two offsets a tape apart could be the same cell.
+        let wrapped = if self.rest_zero {
+            self.pos < 0 || self.pos >= HEAP_SIZE as isize
+        } else {
+            self.highest - self.lowest >= HEAP_SIZE as isize
+        };
+        if wrapped {
+            *self = KnownTape::unknown();
+        }
+    }
+}
+
+fn eliminate(ast: &[Node]) -> Vec<Node> {
+    // the body of a loop starts with nothing known about the tape
+    let mut stack = vec![Level { ops: ast.iter(), result: Vec::new(), span: (0, 0), state: KnownTape::at_start() }];
+    loop {
+        let top = stack.last_mut().unwrap();
+        let known = &mut top.state;
+        let node = match top.ops.next() {
+            Some(node) => node,
+            None => {
+                let done = stack.pop().unwrap();
+                match stack.last_mut() {
+                    Some(parent) => parent.result.push(Node::new(Op::While { ops: done.result }, done.span)),
+                    None => return done.result,
+                }
+                continue;
+            }
+        };
+        match node.op {
+            Op::IncPointer(n) => known.move_by(n as isize),
+            Op::DecPointer(n) => known.move_by(-(n as isize)),
+            Op::IncVal(n) => {
+                let val = known.get().map(|v| v.wrapping_add(n));
+                known.set(val);
+            }
+            Op::DecVal(n) => {
+                let val = known.get().map(|v| v.wrapping_sub(n));
+                known.set(val);
+            }
+            Op::SetRegisterToZero => {
+                if known.get() == Some(0) {
+                    continue;
+                }
+                known.set(Some(0));
+            }
+            Op::Read => known.set(None),
+            Op::Print | Op::PrintBytes(_) => (),
+            Op::InitTape { ref data, ptr } => {
+                *known = KnownTape::at_start();
+                for (i, v) in data.iter().enumerate() {
+                    known.cells.insert(i as isize, Some(*v));
+                }
+                known.pos = ptr as isize;
+            }
+            Op::While { ref ops } => {
+                if known.get() == Some(0) {
+                    continue;
+                }
+                *known = KnownTape::after_loop();
+                stack.push(Level { ops: ops.iter(), result: Vec::new(), span: node.span, state: KnownTape::unknown() });
+                continue;
+            }
+        }
+        top.result.push(node.clone());
+    }
+}
+
+/// Removes loops that can never be entered and clears of cells that are
+/// already zero, e.g. an initial comment loop or a loop directly following
+/// another loop.
+pub fn remove_dead_code(ast: &[Node]) -> Vec<Node> {
+    eliminate(ast)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ast(code: &str) -> Vec<Node> {
+        compact(&get_ast(&code.chars().collect::<Vec<_>>()).0)
+    }
+
+    fn loops(ast: &[Node]) -> usize {
+        ast.iter().filter(|n| matches!(n.op, Op::While { .. })).count()
+    }
+
+    #[test]
+    fn removes_loops_over_known_zero_cells() {
+        assert_eq!(loops(&remove_dead_code(&ast("[.]+>[.]<[.-][.]"))), 1);
+    }
+
+    fn nested(depth: usize) -> Vec<char> {
+        format!("+{}-{}.", "[".repeat(depth), "]".repeat(depth)).chars().collect()
+    }
+
+    #[test]
+    fn limits_nesting_for_recursive_backends_only() {
+        assert_eq!(check_nesting(&nested(MAX_NESTING)), Ok(()));
+        assert_eq!(check_nesting(&nested(MAX_NESTING + 1)), Err("loops nested more than 1000 deep at 1001".to_owned()));
+        assert_eq!(check_brackets(&nested(MAX_NESTING + 1)), Ok(()));
+    }
+
+    #[test]
+    fn parses_copies_and_drops_deeply_nested_programs() {
+        let code = nested(200_000);
+        let (ast, len) = get_ast(&code);
+        assert_eq!(len, code.len());
+        let copy = ast.clone();
+        assert_eq!(copy[1].span, (1, code.len() - 1));
+        let mut depth = 0;
+        let mut ops = &copy[..];
+        while let Some(body) = ops.iter().find_map(|n| match n.op {
+            Op::While { ops: ref body } => Some(body),
+            _ => None,
+        }) {
+            depth += 1;
+            ops = body;
+        }
+        assert_eq!(depth, 200_000);
+    }
+
+    #[test]
+    fn keeps_loops_the_pointer_reaches_by_wrapping_around() {
+        let right = ">".repeat(HEAP_SIZE);
+        let left = "<".repeat(HEAP_SIZE);
+        for code in &[format!("+{}[.-]", right), format!("<+{}[.-]", right), format!("+[-]{}+{}[.-]", right, left)] {
+            let ast = ast(code);
+            assert_eq!(loops(&remove_dead_code(&ast)), loops(&ast), "{}", &code[..6]);
+        }
+    }
 }
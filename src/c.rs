@@ -0,0 +1,168 @@
+use brainfuck::*;
+use std::fmt::Write;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CellType {
+    U8,
+    U16,
+    U32,
+}
+
+impl CellType {
+    pub fn from_name(name: &str) -> Option<CellType> {
+        match name {
+            "u8" => Some(CellType::U8),
+            "u16" => Some(CellType::U16),
+            "u32" => Some(CellType::U32),
+            _ => None,
+        }
+    }
+
+    fn c_type(&self) -> &'static str {
+        match *self {
+            CellType::U8 => "uint8_t",
+            CellType::U16 => "uint16_t",
+            CellType::U32 => "uint32_t",
+        }
+    }
+}
+
+/// What `,` stores in the current cell once the input is exhausted.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EofBehaviour {
+    Zero,
+    MinusOne,
+    Unchanged,
+}
+
+impl EofBehaviour {
+    pub fn from_name(name: &str) -> Option<EofBehaviour> {
+        match name {
+            "zero" => Some(EofBehaviour::Zero),
+            "minus-one" => Some(EofBehaviour::MinusOne),
+            "unchanged" => Some(EofBehaviour::Unchanged),
+            _ => None,
+        }
+    }
+}
+
+/// The `Op` tree assumes 8-bit cells, so wider cells should be combined
+/// with passes that neither fold counts nor evaluate cell values at compile
+/// time, and a tape shorter than `HEAP_SIZE` with passes that don't
+/// initialise it. `tape_size` must not be 0.
+#[derive(PartialEq, Debug)]
+pub struct Options {
+    pub tape_size: usize,
+    pub cell_type: CellType,
+    pub eof: EofBehaviour,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            tape_size: HEAP_SIZE,
+            cell_type: CellType::U8,
+            eof: EofBehaviour::Zero,
+        }
+    }
+}
+
+fn c_string(bytes: &[u8]) -> String {
+    let mut result = String::from("\"");
+    for b in bytes {
+        match *b {
+            b'"' => result.push_str("\\\""),
+            b'\\' => result.push_str("\\\\"),
+            b'\n' => result.push_str("\\n"),
+            b' '..=b'~' => result.push(*b as char),
+            b => write!(result, "\\{:03o}", b).unwrap(),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn to_c_block(ops: &[Node], options: &Options, indent: usize, out: &mut String) {
+    let pad = "    ".repeat(indent);
+    let size = options.tape_size;
+    for node in ops {
+        match node.op {
+            // the pointer wraps around at both ends of the tape
+            Op::IncPointer(n) => {
+                writeln!(out, "{}p = tape + (p - tape + {}) % {};", pad, n % size, size).unwrap()
+            }
+            Op::DecPointer(n) => {
+                writeln!(out, "{}p = tape + (p - tape + {}) % {};", pad, size - n % size, size).unwrap()
+            }
+            Op::IncVal(n) => writeln!(out, "{}*p += {};", pad, n).unwrap(),
+            Op::DecVal(n) => writeln!(out, "{}*p -= {};", pad, n).unwrap(),
+            Op::SetRegisterToZero => writeln!(out, "{}*p = 0;", pad).unwrap(),
+            Op::Print => writeln!(out, "{}putchar(*p);", pad).unwrap(),
+            Op::Read => {
+                writeln!(out, "{}c = getchar();", pad).unwrap();
+                match options.eof {
+                    EofBehaviour::Zero => writeln!(out, "{}*p = c == EOF ? 0 : c;", pad).unwrap(),
+                    EofBehaviour::MinusOne => writeln!(out, "{}*p = c;", pad).unwrap(),
+                    EofBehaviour::Unchanged => writeln!(out, "{}if (c != EOF) *p = c;", pad).unwrap(),
+                }
+            }
+            Op::InitTape { ref data, ptr } => {
+                // C has no empty initialisers
+                if !data.is_empty() {
+                    let cells: Vec<String> = data.iter().map(|v| v.to_string()).collect();
+                    writeln!(out, "{}{{", pad).unwrap();
+                    writeln!(out, "{}    static const cell init[] = {{{}}};", pad, cells.join(", ")).unwrap();
+                    writeln!(out, "{}    memcpy(tape, init, sizeof init);", pad).unwrap();
+                    writeln!(out, "{}}}", pad).unwrap();
+                }
+                writeln!(out, "{}p = tape + {};", pad, ptr).unwrap();
+            }
+            Op::PrintBytes(ref bytes) => {
+                writeln!(out, "{}fwrite({}, 1, {}, stdout);", pad, c_string(bytes), bytes.len()).unwrap()
+            }
+            Op::While { ref ops } => {
+                writeln!(out, "{}while (*p) {{", pad).unwrap();
+                to_c_block(ops, options, indent + 1, out);
+                writeln!(out, "{}}}", pad).unwrap();
+            }
+        }
+    }
+}
+
+/// Translates the program to a standalone C source file.
+pub fn to_c(ops: &[Node], options: &Options) -> String {
+    let mut out = String::new();
+    out.push_str("#include <stdint.h>\n#include <stdio.h>\n#include <string.h>\n\n");
+    writeln!(out, "typedef {} cell;\n", options.cell_type.c_type()).unwrap();
+    writeln!(out, "static cell tape[{}];\n", options.tape_size).unwrap();
+    out.push_str("int main(void) {\n    cell *p = tape;\n    int c;\n    (void)p;\n    (void)c;\n");
+    to_c_block(ops, options, 1, &mut out);
+    out.push_str("    return 0;\n}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(op: Op) -> Node {
+        Node::new(op, (0, 0))
+    }
+
+    #[test]
+    fn pointer_moves_wrap_around_the_tape() {
+        let options = Options { tape_size: 100, ..Options::default() };
+        let c = to_c(&[node(Op::DecPointer(3)), node(Op::IncPointer(250))], &options);
+        assert!(c.contains("p = tape + (p - tape + 97) % 100;"));
+        assert!(c.contains("p = tape + (p - tape + 50) % 100;"));
+    }
+
+    #[test]
+    fn empty_tape_initialisation_only_moves_the_pointer() {
+        let c = to_c(&[node(Op::InitTape { data: vec![], ptr: 3 })], &Options::default());
+        assert!(!c.contains("init"));
+        assert!(c.contains("p = tape + 3;"));
+        let c = to_c(&[node(Op::InitTape { data: vec![1, 2], ptr: 0 })], &Options::default());
+        assert!(c.contains("static const cell init[] = {1, 2};"));
+    }
+}
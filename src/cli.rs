@@ -0,0 +1,797 @@
+use brainfuck::{check_brackets, check_nesting, line_col, Node, HEAP_SIZE};
+use c;
+use coverage;
+use debugger::{Debugger, Stop};
+use elf;
+use interpreter::{self, Io};
+use jit;
+use js;
+use jvm;
+use llvm;
+use passes::{Pass, PassManager};
+use profile;
+use rust;
+use snapshot::Snapshot;
+use std::fs;
+use std::path::Path;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read, Write};
+use std::time::Instant;
+use trace;
+use vm;
+use super::optimise;
+use wasm;
+
+const USAGE: &str = "usage: brainfuck_webassembly [-O0|-O1|-O2|-O3] [-f<pass>|-fno-<pass>] \
+[--emit=wasm|c|elf|rust|js|llvm|class [--instrument-coverage] | --run [--engine=vm|tree|jit] [--checkpoint=FILE [--checkpoint-every=N]] [--resume=FILE] | --trace [--trace-range=START..END] [--trace-sample=N] | --profile[=folded] | --coverage | --bench | --debug] [--tape-size=N] [--cell=u8|u16|u32] [--eof=zero|minus-one|unchanged] \
+[-o <output>] <program.bf|->";
+
+enum Emit {
+    Wasm,
+    C,
+    Elf,
+    Rust,
+    Js,
+    Llvm,
+    Class,
+    Run,
+    Trace,
+    Profile { folded: bool },
+    Coverage,
+    Bench,
+    Debug,
+}
+
+enum Engine {
+    Vm,
+    Tree,
+    Jit,
+}
+
+const ENGINES: [(&str, Engine); 3] = [("vm", Engine::Vm), ("tree", Engine::Tree), ("jit", Engine::Jit)];
+
+// Input is always exhausted and output is dropped.
+struct NullIo;
+
+impl Io for NullIo {
+    fn print(&mut self, _: u8) {}
+
+    fn read(&mut self) -> u8 {
+        0
+    }
+}
+
+struct StdIo {
+    stdin: io::Stdin,
+    stdout: io::Stdout,
+}
+
+impl Io for StdIo {
+    fn print(&mut self, val: u8) {
+        self.write(&[val]);
+    }
+
+    fn read(&mut self) -> u8 {
+        self.stdout.flush().unwrap();
+        let mut byte = [0];
+        match self.stdin.read(&mut byte) {
+            Ok(1) => byte[0],
+            _ => 0,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.stdout.write_all(bytes).unwrap();
+    }
+}
+
+const DEFAULT_CHECKPOINT_EVERY: u64 = 1_000_000_000;
+
+struct Args {
+    flags: Vec<String>,
+    emit: Emit,
+    engine: Engine,
+    c_options: c::Options,
+    trace: trace::Options,
+    instrument_coverage: bool,
+    checkpoint: Option<String>,
+    checkpoint_every: u64,
+    resume: Option<String>,
+    output: Option<String>,
+    input: String,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut parsed = Args {
+        flags: Vec::new(),
+        emit: Emit::Wasm,
+        engine: Engine::Vm,
+        c_options: c::Options::default(),
+        trace: trace::Options::default(),
+        instrument_coverage: false,
+        checkpoint: None,
+        checkpoint_every: DEFAULT_CHECKPOINT_EVERY,
+        resume: None,
+        output: None,
+        input: String::new(),
+    };
+    let mut input = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (name, value) = match arg.find('=') {
+            Some(i) if arg.starts_with("--") => (&arg[..i], Some(&arg[i + 1..])),
+            _ => (arg.as_str(), None),
+        };
+        match (name, value) {
+            ("--emit", Some("wasm")) => parsed.emit = Emit::Wasm,
+            ("--emit", Some("c")) => parsed.emit = Emit::C,
+            ("--emit", Some("elf")) => parsed.emit = Emit::Elf,
+            ("--emit", Some("rust")) => parsed.emit = Emit::Rust,
+            ("--emit", Some("js")) => parsed.emit = Emit::Js,
+            ("--emit", Some("llvm")) => parsed.emit = Emit::Llvm,
+            ("--emit", Some("class")) => parsed.emit = Emit::Class,
+            ("--run", None) => parsed.emit = Emit::Run,
+            ("--engine", Some("vm")) => parsed.engine = Engine::Vm,
+            ("--engine", Some("tree")) => parsed.engine = Engine::Tree,
+            ("--engine", Some("jit")) => parsed.engine = Engine::Jit,
+            ("--trace", None) => parsed.emit = Emit::Trace,
+            ("--trace-range", Some(range)) => {
+                parsed.trace.range = Some(parse_range(range).ok_or_else(|| format!("invalid range '{}'", range))?)
+            }
+            ("--trace-sample", Some(n)) => {
+                parsed.trace.sample = n.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("invalid sample rate '{}'", n))?
+            }
+            ("--profile", None) => parsed.emit = Emit::Profile { folded: false },
+            ("--profile", Some("folded")) => parsed.emit = Emit::Profile { folded: true },
+            ("--coverage", None) => parsed.emit = Emit::Coverage,
+            ("--instrument-coverage", None) => parsed.instrument_coverage = true,
+            ("--checkpoint", Some(path)) => parsed.checkpoint = Some(path.to_owned()),
+            ("--checkpoint-every", Some(n)) => {
+                parsed.checkpoint_every = n.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("invalid step count '{}'", n))?
+            }
+            ("--resume", Some(path)) => parsed.resume = Some(path.to_owned()),
+            ("--bench", None) => parsed.emit = Emit::Bench,
+            ("--debug", None) => parsed.emit = Emit::Debug,
+            ("--tape-size", Some(n)) => {
+                parsed.c_options.tape_size = n.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("invalid tape size '{}'", n))?
+            }
+            ("--cell", Some(cell)) => {
+                parsed.c_options.cell_type =
+                    c::CellType::from_name(cell).ok_or_else(|| format!("unknown cell type '{}'", cell))?
+            }
+            ("--eof", Some(eof)) => {
+                parsed.c_options.eof =
+                    c::EofBehaviour::from_name(eof).ok_or_else(|| format!("unknown EOF behaviour '{}'", eof))?
+            }
+            ("-o", None) => parsed.output = Some(args.next().ok_or("-o needs a file name")?.clone()),
+            (flag, None) if flag.starts_with("-O") || flag.starts_with("-f") => parsed.flags.push(flag.to_owned()),
+            (file, None) if input.is_none() && (file == "-" || !file.starts_with('-')) => {
+                input = Some(file.to_owned())
+            }
+            _ => return Err(format!("unexpected argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    parsed.input = input.ok_or(USAGE)?;
+    Ok(parsed)
+}
+
+// `start..end` in source chars.
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    let mut parts = range.splitn(2, "..");
+    let start = parts.next()?.parse().ok()?;
+    let end = parts.next()?.parse().ok()?;
+    if start < end { Some((start, end)) } else { None }
+}
+
+fn read_program(path: &str) -> Result<String, String> {
+    let mut code = String::new();
+    if path == "-" {
+        io::stdin().read_to_string(&mut code).map_err(|e| e.to_string())?;
+    } else {
+        code = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(code)
+}
+
+fn write_output(path: &Option<String>, bytes: &[u8]) -> Result<(), String> {
+    match *path {
+        Some(ref path) => fs::write(path, bytes).map_err(|e| format!("{}: {}", path, e)),
+        None => io::stdout().write_all(bytes).map_err(|e| e.to_string()),
+    }
+}
+
+fn run_with(ast: &[Node], engine: &Engine, io: &mut dyn Io) {
+    match *engine {
+        Engine::Vm => vm::run(&vm::compile(ast), io),
+        Engine::Tree => interpreter::run(ast, io),
+        Engine::Jit => jit::run(ast, io),
+    }
+}
+
+fn run_program(code: &str, passes: &PassManager, engine: &Engine) -> Result<(), String> {
+    let (ast, _) = optimise(code, passes);
+    let mut io = StdIo {
+        stdin: io::stdin(),
+        stdout: io::stdout(),
+    };
+    run_with(&ast, engine, &mut io);
+    io.stdout.flush().map_err(|e| e.to_string())
+}
+
+// Counts the bytes read so a snapshot can say where the input was.
+struct CountingIo<'a> {
+    io: &'a mut dyn Io,
+    reads: u64,
+}
+
+impl<'a> Io for CountingIo<'a> {
+    fn print(&mut self, val: u8) {
+        self.io.print(val);
+    }
+
+    fn read(&mut self) -> u8 {
+        self.reads += 1;
+        self.io.read()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.io.write(bytes);
+    }
+}
+
+// Writes through a temporary file and renames it, so an interrupted write
+// leaves the previous snapshot intact.
+fn write_snapshot(path: &str, bytes: &[u8]) -> Result<(), String> {
+    let temp = format!("{}.tmp", path);
+    fs::write(&temp, bytes)
+        .and_then(|_| fs::rename(&temp, path))
+        .map_err(|e| format!("{}: {}", path, e))
+}
+
+// Runs on the vm, saving a snapshot every `checkpoint_every` instructions.
+// A resumed run skips the input the snapshot had already read, so the same
+// input can be fed again.
+fn run_resumable(code: &str, passes: &PassManager, args: &Args) -> Result<(), String> {
+    let (ast, _) = optimise(code, passes);
+    let program = vm::compile(&ast);
+    let mut stdio = StdIo {
+        stdin: io::stdin(),
+        stdout: io::stdout(),
+    };
+    let mut snapshot = match args.resume {
+        Some(ref path) => {
+            let bytes = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+            Snapshot::from_bytes(&bytes, &program).map_err(|e| format!("{}: {}", path, e))?
+        }
+        None => Snapshot { machine: vm::Machine::new(), steps: 0, input_pos: 0, output: vec![] },
+    };
+    io::copy(&mut io::stdin().take(snapshot.input_pos), &mut io::sink()).map_err(|e| e.to_string())?;
+    stdio.write(&snapshot.output);
+    snapshot.output.clear();
+
+    let mut io = CountingIo { io: &mut stdio, reads: snapshot.input_pos };
+    while !snapshot.machine.halted(&program) {
+        snapshot.machine.step(&program, &mut io);
+        snapshot.steps += 1;
+        if let (Some(path), 0) = (&args.checkpoint, snapshot.steps % args.checkpoint_every) {
+            snapshot.input_pos = io.reads;
+            // The output so far goes out before the snapshot says it has,
+            // so a resumed run neither loses nor repeats it; that is also
+            // why the snapshot's own output stays empty.
+            io::stdout().flush().map_err(|e| e.to_string())?;
+            write_snapshot(path, &snapshot.to_bytes(&program))?;
+        }
+    }
+    stdio.stdout.flush().map_err(|e| e.to_string())
+}
+
+// Reports go to the output file, or to stderr next to the program's own
+// output.
+fn report_output(output: &Option<String>) -> Result<Box<dyn Write>, String> {
+    Ok(match *output {
+        Some(ref path) => Box::new(fs::File::create(path).map_err(|e| format!("{}: {}", path, e))?),
+        None => Box::new(io::stderr()),
+    })
+}
+
+fn trace_program(code: &str, passes: &PassManager, options: &trace::Options, output: &Option<String>) -> Result<(), String> {
+    let (ast, _) = optimise(code, passes);
+    let mut out = report_output(output)?;
+    let mut out = io::BufWriter::new(&mut *out);
+    let mut io = StdIo {
+        stdin: io::stdin(),
+        stdout: io::stdout(),
+    };
+    trace::run(&vm::compile(&ast), &mut io, options, &mut out).map_err(|e| e.to_string())?;
+    out.flush().map_err(|e| e.to_string())?;
+    io.stdout.flush().map_err(|e| e.to_string())
+}
+
+const HOTTEST_LOOPS: usize = 10;
+
+fn profile_program(code: &str, passes: &PassManager, folded: bool, output: &Option<String>) -> Result<(), String> {
+    let (ast, _) = optimise(code, passes);
+    let program = vm::compile(&ast);
+    let mut io = StdIo {
+        stdin: io::stdin(),
+        stdout: io::stdout(),
+    };
+    let profile = profile::run(&program, &mut io);
+    io.stdout.flush().map_err(|e| e.to_string())?;
+
+    let source: Vec<char> = code.chars().collect();
+    let mut report = String::new();
+    if folded {
+        report = profile.folded(&source);
+    } else {
+        report.push_str(&format!("{} instructions\n\nhottest loops:\n", profile.steps()));
+        for stats in profile.loops().iter().take(HOTTEST_LOOPS) {
+            let (line, col) = line_col(&source, stats.span.0);
+            let text: String = source[stats.span.0..stats.span.1].iter()
+                .filter(|c| !c.is_whitespace()).take(30).collect();
+            report.push_str(&format!("{:>5}:{:<4} {:<30} {} entries, {} iterations ({:.1} per entry), {} instructions\n",
+                                     line, col, text, stats.entries, stats.iterations,
+                                     stats.average_iterations(), stats.instructions));
+        }
+        report.push('\n');
+        report.push_str(&profile.annotated(&source));
+    }
+    report_output(output)?.write_all(report.as_bytes()).map_err(|e| e.to_string())
+}
+
+// With `-o` the lcov goes to the file and a summary to stderr.
+fn coverage_program(code: &str, passes: &PassManager, input: &str, output: &Option<String>) -> Result<(), String> {
+    let (ast, _) = optimise(code, passes);
+    let program = vm::compile(&ast);
+    let mut io = StdIo {
+        stdin: io::stdin(),
+        stdout: io::stdout(),
+    };
+    let points = coverage::from_profile(&profile::run(&program, &mut io));
+    io.stdout.flush().map_err(|e| e.to_string())?;
+
+    let source: Vec<char> = code.chars().collect();
+    let lcov = coverage::lcov(&source, input, &points);
+    report_output(output)?.write_all(lcov.as_bytes()).map_err(|e| e.to_string())?;
+    if output.is_some() {
+        let (covered, total) = coverage::summary(&points);
+        eprintln!("{} of {} ops covered", covered, total);
+    }
+    Ok(())
+}
+
+// Times every engine on the program, compilation to bytecode or machine
+// code included.
+fn bench(code: &str, passes: &PassManager) -> Result<(), String> {
+    let (ast, _) = optimise(code, passes);
+    for &(name, ref engine) in &ENGINES {
+        let start = Instant::now();
+        run_with(&ast, engine, &mut NullIo);
+        let elapsed = start.elapsed();
+        println!("{:>4}: {:>10.3} ms", name, elapsed.as_secs_f64() * 1000.0);
+    }
+    Ok(())
+}
+
+const DEBUG_HELP: &str = "\
+s [n]          run n instructions (default 1)
+sc             step to the next source position
+n              step over a loop
+f              run until the current loop exits
+c              continue to a breakpoint, watchpoint or the end
+record on|off  record steps so they can be undone
+sb [n]         step back n instructions (default 1)
+rc             run back to a breakpoint, watchpoint or the start
+rw <cell>      run back to the last change of a cell
+rp [byte]      run back to the last print of a byte, or of anything
+b <pos>        break at a source position, `line:col` or a char index
+d <pos>        delete a breakpoint
+w <cell>       stop when a cell changes
+uw <cell>      remove a watchpoint
+marks on|off   whether `#` in the source stops the program
+t [from [n]]   show tape cells, around the pointer by default
+i              show breakpoints and watchpoints
+l              show where the program is
+save <file>    save the program's state
+load <file>    continue from a saved state
+q              quit";
+
+// Program input comes from the same terminal as the commands, a line at a
+// time when the program asks for it.
+struct DebugIo {
+    input: VecDeque<u8>,
+    eof: bool,
+}
+
+impl Io for DebugIo {
+    fn print(&mut self, val: u8) {
+        let mut stdout = io::stdout();
+        stdout.write_all(&[val]).unwrap();
+        stdout.flush().unwrap();
+    }
+
+    fn read(&mut self) -> u8 {
+        if self.input.is_empty() && !self.eof {
+            print!("program input> ");
+            io::stdout().flush().unwrap();
+            let mut line = String::new();
+            match io::stdin().lock().read_line(&mut line) {
+                Ok(0) | Err(_) => self.eof = true,
+                Ok(_) => self.input.extend(line.bytes()),
+            }
+        }
+        self.input.pop_front().unwrap_or(0)
+    }
+}
+
+fn parse_position(source: &[char], pos: &str) -> Option<usize> {
+    let mut parts = pos.splitn(2, ':');
+    let first = parts.next()?.parse::<usize>().ok()?;
+    match parts.next() {
+        None => Some(first),
+        Some(col) => {
+            let col = col.parse::<usize>().ok()?;
+            let line_start = if first <= 1 {
+                0
+            } else {
+                source.iter().enumerate().filter(|&(_, &c)| c == '\n').nth(first - 2)?.0 + 1
+            };
+            Some(line_start + col.max(1) - 1)
+        }
+    }
+}
+
+fn show_location(debugger: &Debugger) {
+    let source = debugger.source();
+    let pos = debugger.location();
+    let (line, col) = line_col(source, pos);
+    println!("{}:{} (pos {}), pc {}, ptr {} = {}, {} steps",
+             line, col, pos, debugger.pc(), debugger.ptr(), debugger.tape()[debugger.ptr()], debugger.steps());
+    let text: String = source.iter().skip(pos + 1 - col).take_while(|&&c| c != '\n').collect();
+    println!("  {}", text);
+    println!("  {}^", " ".repeat(col - 1));
+}
+
+fn show_stop(debugger: &Debugger, stop: Stop) {
+    match stop {
+        Stop::Step => {}
+        Stop::Breakpoint(pos) => println!("breakpoint at {}", pos),
+        Stop::DebugMark(pos) => println!("# at {}", pos),
+        Stop::Watchpoint { cell, old, new } => println!("cell {} changed from {} to {}", cell, old, new),
+        Stop::Halted => {
+            println!("program finished after {} steps", debugger.steps());
+            return;
+        }
+        Stop::Start => println!("start of the recording"),
+    }
+    show_location(debugger);
+}
+
+fn show_tape(debugger: &Debugger, from: Option<usize>, len: usize) {
+    let from = from.unwrap_or_else(|| debugger.ptr().saturating_sub(len / 2));
+    let cells: Vec<String> = (from..(from + len).min(HEAP_SIZE))
+        .map(|i| if i == debugger.ptr() {
+            format!("[{}]", debugger.tape()[i])
+        } else {
+            debugger.tape()[i].to_string()
+        })
+        .collect();
+    println!("{}: {}", from, cells.join(" "));
+}
+
+fn debug_command(debugger: &mut Debugger, io: &mut DebugIo, line: &str) -> Result<bool, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let number = |i: usize| -> Result<Option<usize>, String> {
+        words.get(i).map(|w| w.parse().map_err(|_| format!("not a number: {}", w))).transpose()
+    };
+    let position = |source: &[char], i: usize| -> Result<usize, String> {
+        let word = words.get(i).ok_or("missing position")?;
+        parse_position(source, word).ok_or_else(|| format!("not a position: {}", word))
+    };
+    match words.first().cloned().unwrap_or("s") {
+        "s" => {
+            let mut stop = Stop::Step;
+            for _ in 0..number(1)?.unwrap_or(1) {
+                stop = debugger.step(io);
+                if stop != Stop::Step {
+                    break;
+                }
+            }
+            show_stop(debugger, stop);
+        }
+        "sc" => {
+            let stop = debugger.step_source(io);
+            show_stop(debugger, stop)
+        }
+        "n" => {
+            let stop = debugger.step_over(io);
+            show_stop(debugger, stop)
+        }
+        "f" => {
+            let stop = debugger.step_out(io);
+            show_stop(debugger, stop)
+        }
+        "c" => {
+            let stop = debugger.resume(io);
+            show_stop(debugger, stop)
+        }
+        "record" => debugger.set_recording(words.get(1) != Some(&"off")),
+        "sb" => {
+            let mut stop = Stop::Step;
+            for _ in 0..number(1)?.unwrap_or(1) {
+                stop = debugger.step_back();
+                if stop != Stop::Step {
+                    break;
+                }
+            }
+            show_stop(debugger, stop);
+        }
+        "rc" => {
+            let stop = debugger.resume_back();
+            show_stop(debugger, stop)
+        }
+        "rw" => {
+            let cell = number(1)?.ok_or("missing cell")?;
+            if cell >= HEAP_SIZE {
+                return Err(format!("no cell {}", cell));
+            }
+            let stop = debugger.back_to_write(cell);
+            show_stop(debugger, stop)
+        }
+        "rp" => {
+            let byte = words.get(1).map(|w| w.parse::<u8>().map_err(|_| format!("not a byte: {}", w))).transpose()?;
+            let stop = debugger.back_to_print(byte);
+            show_stop(debugger, stop)
+        }
+        "b" => {
+            let pos = position(debugger.source(), 1)?;
+            if !debugger.add_breakpoint(pos) {
+                return Err(format!("no code at or after {}", pos));
+            }
+        }
+        "d" => {
+            let pos = position(debugger.source(), 1)?;
+            if !debugger.remove_breakpoint(pos) {
+                return Err(format!("no breakpoint at {}", pos));
+            }
+        }
+        "w" => {
+            let cell = number(1)?.ok_or("missing cell")?;
+            if !debugger.watch(cell) {
+                return Err(format!("can't watch cell {}", cell));
+            }
+        }
+        "uw" => {
+            let cell = number(1)?.ok_or("missing cell")?;
+            if !debugger.unwatch(cell) {
+                return Err(format!("cell {} is not watched", cell));
+            }
+        }
+        "marks" => debugger.break_on_marks = words.get(1) != Some(&"off"),
+        "t" => show_tape(debugger, number(1)?, number(2)?.unwrap_or(16)),
+        "i" => {
+            println!("breakpoints: {:?}", debugger.breakpoints());
+            println!("watchpoints: {:?}", debugger.watchpoints());
+            println!("{} instructions, # marks {}", debugger.program().code.len(),
+                     if debugger.break_on_marks { "on" } else { "off" });
+            if debugger.recording() {
+                println!("recording, {} steps can be undone", debugger.recorded_steps());
+            }
+        }
+        "l" => show_location(debugger),
+        "save" => {
+            let path = words.get(1).ok_or("missing file name")?;
+            write_snapshot(path, &debugger.snapshot().to_bytes(debugger.program()))?;
+        }
+        "load" => {
+            let path = words.get(1).ok_or("missing file name")?;
+            let bytes = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+            let snapshot = Snapshot::from_bytes(&bytes, debugger.program()).map_err(|e| format!("{}: {}", path, e))?;
+            debugger.restore(snapshot);
+            show_location(debugger);
+        }
+        "q" => return Ok(false),
+        "h" | "help" => println!("{}", DEBUG_HELP),
+        other => return Err(format!("unknown command '{}', try 'h'", other)),
+    }
+    Ok(true)
+}
+
+// An empty line repeats `s`.
+fn debug(code: &str, passes: &PassManager) -> Result<(), String> {
+    let (ast, _) = optimise(code, passes);
+    let mut debugger = Debugger::new(&ast, code);
+    let mut io = DebugIo { input: VecDeque::new(), eof: false };
+    show_location(&debugger);
+    let stdin = io::stdin();
+    loop {
+        print!("(bfdb) ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+            return Ok(());
+        }
+        match debug_command(&mut debugger, &mut io, &line) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+// The passes assume a tape of `HEAP_SIZE` 8-bit cells: `Compact` folds
+// counts and the dead code and partial evaluation passes track values
+// modulo 256, and the tape the partial evaluator fills may not fit.
+fn restrict_passes(passes: &mut PassManager, options: &c::Options) {
+    if options.cell_type != c::CellType::U8 {
+        passes.disable(Pass::Compact);
+        passes.disable(Pass::DeadCode);
+        passes.disable(Pass::PartialEval);
+    }
+    if options.tape_size < HEAP_SIZE {
+        passes.disable(Pass::PartialEval);
+    }
+}
+
+// The generated machine code works on bytes, leaves the cell at 0 when the
+// input is exhausted and moves the pointer by 32-bit immediates.
+fn check_elf_options(options: &c::Options) -> Result<(), String> {
+    if options.cell_type != c::CellType::U8 {
+        return Err("--emit=elf only supports --cell=u8".to_owned());
+    }
+    if options.eof != c::EofBehaviour::Zero {
+        return Err("--emit=elf only supports --eof=zero".to_owned());
+    }
+    if options.tape_size > i32::MAX as usize {
+        return Err(format!("--emit=elf needs a --tape-size below {}", i32::MAX as u64 + 1));
+    }
+    Ok(())
+}
+
+// Whether the program goes through code that recurses once per loop: the
+// backends and every engine but the bytecode VM.
+fn recurses(args: &Args) -> bool {
+    match args.emit {
+        Emit::Run => match args.engine {
+            Engine::Vm => false,
+            Engine::Tree | Engine::Jit => true,
+        },
+        Emit::Trace | Emit::Profile { .. } | Emit::Coverage | Emit::Debug => false,
+        _ => true,
+    }
+}
+
+/// Compiles or runs the program named on the command line.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let args = parse_args(args)?;
+    match args.emit {
+        Emit::C | Emit::Elf => {}
+        // the interpreters, the JIT and the other backends have a fixed tape
+        _ if args.c_options != c::Options::default() => {
+            return Err("--tape-size, --cell and --eof only apply to --emit=c and --emit=elf".to_owned());
+        }
+        _ => {}
+    }
+    let flags = args.flags.join(" ");
+    let mut passes = match args.emit {
+        // the debugger steps through the program as written
+        Emit::Debug => PassManager::for_source(&flags, &[])?,
+        // traces and profiles only merge runs of the same command
+        Emit::Trace | Emit::Profile { .. } => PassManager::for_source(&flags, &[Pass::Compact])?,
+        // coverage reports every op, including the ones that never run
+        Emit::Coverage => PassManager::for_source(&flags, &[])?,
+        Emit::Wasm if args.instrument_coverage => PassManager::for_source(&flags, &[])?,
+        _ => PassManager::from_flags(&flags)?,
+    };
+    let code = read_program(&args.input)?;
+    let chars: Vec<char> = code.chars().collect();
+    check_brackets(&chars).map_err(|e| format!("{}: {}", args.input, e))?;
+    if recurses(&args) {
+        check_nesting(&chars).map_err(|e| format!("{}: {}", args.input, e))?;
+    }
+
+    let output = match args.emit {
+        Emit::Wasm => {
+            let (ast, _) = optimise(&code, &passes);
+            if args.instrument_coverage {
+                wasm::to_wasm_with_coverage(&ast, &passes).0
+            } else {
+                wasm::to_wasm(&ast, &passes, None)
+            }
+        }
+        Emit::C => {
+            restrict_passes(&mut passes, &args.c_options);
+            let (ast, _) = optimise(&code, &passes);
+            c::to_c(&ast, &args.c_options).into_bytes()
+        }
+        Emit::Elf => {
+            check_elf_options(&args.c_options)?;
+            restrict_passes(&mut passes, &args.c_options);
+            let (ast, _) = optimise(&code, &passes);
+            elf::to_elf(&ast, args.c_options.tape_size)
+        }
+        Emit::Rust => {
+            let (ast, _) = optimise(&code, &passes);
+            rust::to_rust(&ast).into_bytes()
+        }
+        Emit::Js => {
+            let (ast, _) = optimise(&code, &passes);
+            js::to_js(&ast).into_bytes()
+        }
+        Emit::Llvm => {
+            let (ast, _) = optimise(&code, &passes);
+            llvm::to_llvm(&ast).into_bytes()
+        }
+        Emit::Class => {
+            let (ast, _) = optimise(&code, &passes);
+            jvm::to_class(&ast, &class_name(&args.output))?
+        }
+        Emit::Run if args.checkpoint.is_some() || args.resume.is_some() => {
+            if let Engine::Vm = args.engine {
+                return run_resumable(&code, &passes, &args);
+            }
+            return Err("only the vm engine can checkpoint or resume".to_owned());
+        }
+        Emit::Run => return run_program(&code, &passes, &args.engine),
+        Emit::Trace => return trace_program(&code, &passes, &args.trace, &args.output),
+        Emit::Profile { folded } => return profile_program(&code, &passes, folded, &args.output),
+        Emit::Coverage => return coverage_program(&code, &passes, &args.input, &args.output),
+        Emit::Bench => return bench(&code, &passes),
+        Emit::Debug => return debug(&code, &passes),
+    };
+    write_output(&args.output, &output)?;
+    if let (Emit::Elf, Some(path)) = (args.emit, args.output) {
+        make_executable(&path)?;
+    }
+    Ok(())
+}
+
+// The JVM only loads `Foo` from `Foo.class`.
+fn class_name(output: &Option<String>) -> String {
+    output.as_ref()
+        .and_then(|path| Path::new(path).file_stem())
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Main".to_owned())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &str) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|e| format!("{}: {}", path, e))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn only_c_and_elf_take_a_tape_configuration() {
+        let error = Err("--tape-size, --cell and --eof only apply to --emit=c and --emit=elf".to_owned());
+        assert_eq!(run(&args("--run --engine=jit --tape-size=100 missing.bf")), error);
+        assert_eq!(run(&args("--run --cell=u16 missing.bf")), error);
+        assert_eq!(run(&args("--emit=wasm --eof=unchanged missing.bf")), error);
+        assert!(run(&args("--emit=c --tape-size=100 missing.bf")).unwrap_err().starts_with("missing.bf: "));
+    }
+
+    #[test]
+    fn source_modes_refuse_passes_that_remove_code() {
+        let error = Err("dead-code removes code; use -O0, -O1 or -fcompact".to_owned());
+        assert_eq!(run(&args("-O3 --debug missing.bf")), error);
+        assert_eq!(run(&args("-O2 --trace missing.bf")), error);
+        assert_eq!(run(&args("--profile=folded -fdead-code missing.bf")), error);
+        assert_eq!(run(&args("--coverage -O3 missing.bf")), error);
+        assert_eq!(run(&args("--emit=wasm --instrument-coverage -O2 missing.bf")), error);
+        assert!(run(&args("--emit=wasm -O2 missing.bf")).unwrap_err().starts_with("missing.bf: "));
+        assert!(run(&args("-O1 --debug missing.bf")).unwrap_err().starts_with("missing.bf: "));
+        assert!(run(&args("--trace missing.bf")).unwrap_err().starts_with("missing.bf: "));
+    }
+}
@@ -0,0 +1,130 @@
+// Which ops and loop bodies a run executed, as an lcov tracefile. Loop
+// bodies are reported as functions named after the loop's position, ops as
+// the lines they are on.
+use brainfuck::*;
+use profile::Profile;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use vm::Instr;
+
+/// Something a run can execute.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Point {
+    Op(Span),
+    /// The body of the loop with this span.
+    LoopBody(Span),
+}
+
+/// Execution counts from the bytecode VM. The test of a loop counts as an
+/// op at its `[`, the jump back as one at its `]`.
+pub fn from_profile(profile: &Profile) -> Vec<(Point, u64)> {
+    let program = profile.program();
+    let mut points = vec![];
+    for (pc, &(start, end)) in program.spans.iter().enumerate() {
+        let count = profile.counts[pc];
+        match program.code[pc] {
+            Instr::JumpIfZero(after) => {
+                points.push((Point::Op((start, start + 1)), count));
+                points.push((Point::LoopBody((start, end)), profile.counts[after - 1]));
+            }
+            Instr::JumpIfNotZero(_) => points.push((Point::Op((end - 1, end)), count)),
+            _ => points.push((Point::Op((start, end)), count)),
+        }
+    }
+    points
+}
+
+/// `(covered, total)` ops.
+pub fn summary(points: &[(Point, u64)]) -> (usize, usize) {
+    let ops: Vec<u64> = points.iter()
+        .filter_map(|&(point, count)| match point {
+            Point::Op(_) => Some(count),
+            Point::LoopBody(_) => None,
+        })
+        .collect();
+    (ops.iter().filter(|&&count| count > 0).count(), ops.len())
+}
+
+/// An lcov record for `file`. A line's count is that of its most executed
+/// op.
+pub fn lcov(source: &[char], file: &str, points: &[(Point, u64)]) -> String {
+    let mut res = String::new();
+    writeln!(res, "TN:").unwrap();
+    writeln!(res, "SF:{}", file).unwrap();
+
+    let mut lines: BTreeMap<usize, u64> = BTreeMap::new();
+    let mut loops = vec![];
+    for &(point, count) in points {
+        match point {
+            Point::Op((start, _)) => {
+                let line = lines.entry(line_col(source, start).0).or_insert(0);
+                *line = (*line).max(count);
+            }
+            Point::LoopBody((start, _)) => loops.push((line_col(source, start), count)),
+        }
+    }
+    for &((line, col), _) in &loops {
+        writeln!(res, "FN:{},loop@{}:{}", line, line, col).unwrap();
+    }
+    for &((line, col), count) in &loops {
+        writeln!(res, "FNDA:{},loop@{}:{}", count, line, col).unwrap();
+    }
+    writeln!(res, "FNF:{}", loops.len()).unwrap();
+    writeln!(res, "FNH:{}", loops.iter().filter(|&&(_, count)| count > 0).count()).unwrap();
+    for (line, count) in &lines {
+        writeln!(res, "DA:{},{}", line, count).unwrap();
+    }
+    writeln!(res, "LF:{}", lines.len()).unwrap();
+    writeln!(res, "LH:{}", lines.values().filter(|&&count| count > 0).count()).unwrap();
+    writeln!(res, "end_of_record").unwrap();
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interpreter::Io;
+    use profile;
+    use vm;
+
+    struct NullIo;
+
+    impl Io for NullIo {
+        fn print(&mut self, _: u8) {}
+
+        fn read(&mut self) -> u8 {
+            0
+        }
+    }
+
+    const PROGRAM: &str = "[never . run]\n+++[>+<-]\n>.\n[-]<[never]";
+
+    #[test]
+    fn reports_loops_that_never_run() {
+        let source: Vec<char> = PROGRAM.chars().collect();
+        let program = vm::compile(&get_ast(&source).0);
+        let points = from_profile(&profile::run(&program, &mut NullIo));
+        assert_eq!(summary(&points), (17, 20));
+        assert_eq!(lcov(&source, "loops.bf", &points), "\
+TN:
+SF:loops.bf
+FN:1,loop@1:1
+FN:2,loop@2:4
+FN:4,loop@4:1
+FN:4,loop@4:5
+FNDA:0,loop@1:1
+FNDA:3,loop@2:4
+FNDA:3,loop@4:1
+FNDA:0,loop@4:5
+FNF:4
+FNH:2
+DA:1,1
+DA:2,3
+DA:3,1
+DA:4,3
+LF:4
+LH:4
+end_of_record
+");
+    }
+}
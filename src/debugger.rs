@@ -0,0 +1,479 @@
+// Runs a `vm::Machine` an instruction at a time. Source positions are char
+// indices into the program text, the same ones `Span`s use.
+use brainfuck::*;
+use interpreter::Io;
+use std::collections::{BTreeSet, HashMap};
+use snapshot::Snapshot;
+use vm::{self, Instr, Machine, Program};
+
+/// Why the debugger handed control back.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Stop {
+    /// The requested step finished.
+    Step,
+    /// About to run the code at a breakpoint's source position.
+    Breakpoint(usize),
+    /// About to run the code after the `#` at this position.
+    DebugMark(usize),
+    /// The last instruction changed a watched cell.
+    Watchpoint { cell: usize, old: u8, new: u8 },
+    Halted,
+    /// Ran back to the oldest recorded step.
+    Start,
+}
+
+// Enough to undo one instruction. Redoing it runs it again, except for a
+// read whose byte comes from `write`.
+struct Change {
+    pc: usize,
+    ptr: usize,
+    /// Cell, value before and value after.
+    write: Option<(usize, u8, u8)>,
+    /// The whole tape before an `InitTape`.
+    tape: Option<Vec<u8>>,
+}
+
+// Output is shown once; replaying recorded steps prints nothing.
+struct Replay;
+
+impl Io for Replay {
+    fn print(&mut self, _: u8) {}
+
+    fn read(&mut self) -> u8 {
+        unreachable!("reads are replayed from the history")
+    }
+}
+
+pub struct Debugger {
+    program: Program,
+    machine: Machine,
+    source: Vec<char>,
+    /// Breakpoints by the instruction they stop before.
+    breakpoints: HashMap<usize, usize>,
+    marks: HashMap<usize, usize>,
+    watchpoints: BTreeSet<usize>,
+    steps: u64,
+    /// Bytes read so far.
+    input_pos: u64,
+    history: Vec<Change>,
+    /// Steps in `history` that have not been undone.
+    now: usize,
+    recording: bool,
+    /// Whether `#` in the source acts as a breakpoint.
+    pub break_on_marks: bool,
+}
+
+impl Debugger {
+    pub fn new(ops: &[Node], source: &str) -> Debugger {
+        let mut debugger = Debugger {
+            program: vm::compile(ops),
+            machine: Machine::new(),
+            source: source.chars().collect(),
+            breakpoints: HashMap::new(),
+            marks: HashMap::new(),
+            watchpoints: BTreeSet::new(),
+            steps: 0,
+            input_pos: 0,
+            history: Vec::new(),
+            now: 0,
+            recording: false,
+            break_on_marks: true,
+        };
+        for (pos, _) in debugger.source.iter().enumerate().filter(|&(_, &c)| c == '#') {
+            if let Some(pc) = debugger.pc_at(pos) {
+                debugger.marks.entry(pc).or_insert(pos);
+            }
+        }
+        debugger
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn source(&self) -> &[char] {
+        &self.source
+    }
+
+    pub fn tape(&self) -> &[u8] {
+        &self.machine.tape
+    }
+
+    pub fn ptr(&self) -> usize {
+        self.machine.ptr
+    }
+
+    /// The next instruction to run.
+    pub fn pc(&self) -> usize {
+        self.machine.pc
+    }
+
+    /// Instructions run so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn halted(&self) -> bool {
+        self.machine.halted(&self.program)
+    }
+
+    fn location_of(&self, pc: usize) -> usize {
+        match (self.program.code.get(pc), self.program.spans.get(pc)) {
+            // the `]` of a loop
+            (Some(&Instr::JumpIfNotZero(_)), Some(&(_, end))) => end - 1,
+            (_, Some(&(start, _))) => start,
+            _ => self.source.len(),
+        }
+    }
+
+    /// Source position of the next instruction, the length of the source
+    /// once halted.
+    pub fn location(&self) -> usize {
+        self.location_of(self.machine.pc)
+    }
+
+    // The first instruction at or after `pos`.
+    fn pc_at(&self, pos: usize) -> Option<usize> {
+        (0..self.program.code.len()).find(|&pc| self.location_of(pc) >= pos)
+    }
+
+    /// Stops before the first instruction at or after `pos`; false when no
+    /// code follows it.
+    pub fn add_breakpoint(&mut self, pos: usize) -> bool {
+        match self.pc_at(pos) {
+            Some(pc) => {
+                self.breakpoints.insert(pc, pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, pos: usize) -> bool {
+        let len = self.breakpoints.len();
+        self.breakpoints.retain(|_, &mut p| p != pos);
+        self.breakpoints.len() != len
+    }
+
+    pub fn breakpoints(&self) -> BTreeSet<usize> {
+        self.breakpoints.values().cloned().collect()
+    }
+
+    /// Stops after any instruction that changes `cell`.
+    pub fn watch(&mut self, cell: usize) -> bool {
+        cell < HEAP_SIZE && self.watchpoints.insert(cell)
+    }
+
+    pub fn unwatch(&mut self, cell: usize) -> bool {
+        self.watchpoints.remove(&cell)
+    }
+
+    pub fn watchpoints(&self) -> &BTreeSet<usize> {
+        &self.watchpoints
+    }
+
+    /// Records every step from now on so it can be undone; turning it off
+    /// forgets the history.
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recording = recording;
+        if !recording {
+            self.history.clear();
+            self.now = 0;
+        }
+    }
+
+    pub fn recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Steps that can be undone.
+    pub fn recorded_steps(&self) -> usize {
+        self.now
+    }
+
+    /// The current state; program output has all been delivered.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            machine: self.machine.clone(),
+            steps: self.steps,
+            input_pos: self.input_pos,
+            output: Vec::new(),
+        }
+    }
+
+    /// Continues from `snapshot`, forgetting the recorded history.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.machine = snapshot.machine;
+        self.steps = snapshot.steps;
+        self.input_pos = snapshot.input_pos;
+        self.history.clear();
+        self.now = 0;
+    }
+
+    fn forward(&mut self, io: &mut dyn Io) {
+        self.steps += 1;
+        if self.program.code[self.machine.pc] == Instr::Read {
+            self.input_pos += 1;
+        }
+        if self.now < self.history.len() {
+            let write = self.history[self.now].write;
+            self.now += 1;
+            match (self.program.code[self.machine.pc], write) {
+                (Instr::Read, Some((cell, _, new))) => {
+                    self.machine.tape[cell] = new;
+                    self.machine.pc += 1;
+                }
+                (Instr::Read, None) => self.machine.pc += 1,
+                _ => self.machine.step(&self.program, &mut Replay),
+            }
+            return;
+        }
+        if !self.recording {
+            self.machine.step(&self.program, io);
+            return;
+        }
+        let (pc, ptr) = (self.machine.pc, self.machine.ptr);
+        let before = self.machine.tape[ptr];
+        let tape = match self.program.code[pc] {
+            Instr::InitTape(_) => Some(self.machine.tape.clone()),
+            _ => None,
+        };
+        self.machine.step(&self.program, io);
+        let after = self.machine.tape[ptr];
+        let write = if after != before { Some((ptr, before, after)) } else { None };
+        self.history.push(Change { pc, ptr, write, tape });
+        self.now += 1;
+    }
+
+    fn backward(&mut self) {
+        self.steps -= 1;
+        self.now -= 1;
+        let change = &self.history[self.now];
+        if self.program.code[change.pc] == Instr::Read {
+            self.input_pos -= 1;
+        }
+        self.machine.pc = change.pc;
+        self.machine.ptr = change.ptr;
+        if let Some((cell, old, _)) = change.write {
+            self.machine.tape[cell] = old;
+        }
+        if let Some(ref tape) = change.tape {
+            self.machine.tape.copy_from_slice(tape);
+        }
+    }
+
+    // Runs forwards, or backwards through the history, until `done` holds
+    // or something else stops the program.
+    fn run_until<F: Fn(&Debugger) -> bool>(&mut self, io: &mut dyn Io, reverse: bool, done: F) -> Stop {
+        loop {
+            if reverse && self.now == 0 {
+                return Stop::Start;
+            }
+            if !reverse && self.halted() {
+                return Stop::Halted;
+            }
+            let watched: Vec<(usize, u8)> = self.watchpoints.iter().map(|&c| (c, self.machine.tape[c])).collect();
+            if reverse {
+                self.backward();
+            } else {
+                self.forward(io);
+            }
+            for (cell, old) in watched {
+                let new = self.machine.tape[cell];
+                if new != old {
+                    return if reverse {
+                        Stop::Watchpoint { cell, old: new, new: old }
+                    } else {
+                        Stop::Watchpoint { cell, old, new }
+                    };
+                }
+            }
+            if !reverse && self.halted() {
+                return Stop::Halted;
+            }
+            if let Some(&pos) = self.breakpoints.get(&self.machine.pc) {
+                return Stop::Breakpoint(pos);
+            }
+            if let (true, Some(&pos)) = (self.break_on_marks, self.marks.get(&self.machine.pc)) {
+                return Stop::DebugMark(pos);
+            }
+            if done(self) {
+                return Stop::Step;
+            }
+        }
+    }
+
+    /// Runs one instruction.
+    pub fn step(&mut self, io: &mut dyn Io) -> Stop {
+        self.run_until(io, false, |_| true)
+    }
+
+    /// Runs until the next instruction comes from another source position.
+    /// Ops merged by the optimiser still run as a whole.
+    pub fn step_source(&mut self, io: &mut dyn Io) -> Stop {
+        let location = self.location();
+        self.run_until(io, false, |d| d.location() != location)
+    }
+
+    /// Like `step`, but runs a loop that starts here to its end.
+    pub fn step_over(&mut self, io: &mut dyn Io) -> Stop {
+        match self.program.code.get(self.machine.pc) {
+            Some(&Instr::JumpIfZero(after)) => self.run_until(io, false, |d| d.machine.pc == after),
+            _ => self.step(io),
+        }
+    }
+
+    /// Runs until the innermost loop around the next instruction exits.
+    pub fn step_out(&mut self, io: &mut dyn Io) -> Stop {
+        let pc = self.machine.pc;
+        let after = self.program.code[..pc.min(self.program.code.len())].iter().rev()
+            .filter_map(|instr| match *instr {
+                Instr::JumpIfZero(after) if after > pc => Some(after),
+                _ => None,
+            })
+            .next();
+        match after {
+            Some(after) => self.run_until(io, false, |d| d.machine.pc == after),
+            None => self.resume(io),
+        }
+    }
+
+    /// Runs until a breakpoint, watchpoint or the end of the program.
+    pub fn resume(&mut self, io: &mut dyn Io) -> Stop {
+        self.run_until(io, false, |_| false)
+    }
+
+    /// Undoes the last recorded instruction.
+    pub fn step_back(&mut self) -> Stop {
+        self.run_until(&mut Replay, true, |_| true)
+    }
+
+    /// Runs backwards to a breakpoint, a watchpoint or the start of the
+    /// recording.
+    pub fn resume_back(&mut self) -> Stop {
+        self.run_until(&mut Replay, true, |_| false)
+    }
+
+    /// Runs backwards until the next instruction is the last one that
+    /// changed `cell`.
+    pub fn back_to_write(&mut self, cell: usize) -> Stop {
+        self.run_until(&mut Replay, true, |d| match d.history[d.now].write {
+            Some((c, _, _)) => c == cell,
+            None => match d.program.code[d.machine.pc] {
+                Instr::InitTape(i) => d.program.tapes[i].0.get(cell).is_some_and(|&v| v != d.machine.tape[cell]),
+                _ => false,
+            },
+        })
+    }
+
+    /// Runs backwards until the next instruction prints `byte`, or prints
+    /// anything when `byte` is `None`.
+    pub fn back_to_print(&mut self, byte: Option<u8>) -> Stop {
+        self.run_until(&mut Replay, true, |d| {
+            let output = d.next_output();
+            match byte {
+                Some(byte) => output.contains(&byte),
+                None => !output.is_empty(),
+            }
+        })
+    }
+
+    // What the next instruction prints.
+    fn next_output(&self) -> &[u8] {
+        match self.program.code.get(self.machine.pc) {
+            Some(&Instr::Print) => &self.machine.tape[self.machine.ptr..self.machine.ptr + 1],
+            Some(&Instr::PrintBytes(i)) => &self.program.strings[i],
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Output(Vec<u8>);
+
+    impl Io for Output {
+        fn print(&mut self, val: u8) {
+            self.0.push(val);
+        }
+
+        fn read(&mut self) -> u8 {
+            0
+        }
+    }
+
+    //                   0123456789012
+    const PROGRAM: &str = "++#+[->+<]#>.";
+
+    fn debugger() -> Debugger {
+        let (ast, _) = get_ast(&PROGRAM.chars().collect::<Vec<_>>());
+        Debugger::new(&ast, PROGRAM)
+    }
+
+    #[test]
+    fn stops_at_marks() {
+        let mut debugger = debugger();
+        let mut io = Output(vec![]);
+        assert_eq!(debugger.resume(&mut io), Stop::DebugMark(2));
+        assert_eq!((debugger.location(), &debugger.tape()[..2]), (3, &[2, 0][..]));
+        assert_eq!(debugger.resume(&mut io), Stop::DebugMark(10));
+        assert_eq!((debugger.location(), &debugger.tape()[..2]), (11, &[0, 3][..]));
+        assert_eq!(debugger.resume(&mut io), Stop::Halted);
+        assert_eq!(io.0, [3]);
+
+        let mut debugger = self::debugger();
+        debugger.break_on_marks = false;
+        assert_eq!(debugger.resume(&mut io), Stop::Halted);
+    }
+
+    #[test]
+    fn stops_at_breakpoints_on_every_iteration() {
+        let mut debugger = debugger();
+        let mut io = Output(vec![]);
+        debugger.break_on_marks = false;
+        assert!(debugger.add_breakpoint(5));
+        for counter in &[3, 2, 1] {
+            assert_eq!(debugger.resume(&mut io), Stop::Breakpoint(5));
+            assert_eq!(debugger.tape()[0], *counter);
+        }
+        assert!(debugger.remove_breakpoint(5));
+        assert_eq!(debugger.resume(&mut io), Stop::Halted);
+        assert!(!debugger.add_breakpoint(PROGRAM.len()));
+    }
+
+    #[test]
+    fn watchpoints_report_changes() {
+        let mut debugger = debugger();
+        let mut io = Output(vec![]);
+        debugger.break_on_marks = false;
+        assert!(debugger.watch(1));
+        assert_eq!(debugger.resume(&mut io), Stop::Watchpoint { cell: 1, old: 0, new: 1 });
+        assert_eq!(debugger.location(), 8);
+    }
+
+    #[test]
+    fn runs_backwards_through_the_recording() {
+        let mut debugger = debugger();
+        let mut io = Output(vec![]);
+        debugger.break_on_marks = false;
+        debugger.set_recording(true);
+        assert_eq!(debugger.resume(&mut io), Stop::Halted);
+        let steps = debugger.steps();
+
+        // before the `+` of the last iteration
+        assert_eq!(debugger.back_to_write(1), Stop::Step);
+        assert_eq!((debugger.location(), debugger.ptr(), debugger.tape()[1]), (7, 1, 2));
+        assert_eq!(debugger.step_back(), Stop::Step);
+        assert_eq!((debugger.location(), debugger.ptr()), (6, 0));
+
+        // replaying prints nothing again
+        assert_eq!(debugger.resume(&mut io), Stop::Halted);
+        assert_eq!((debugger.steps(), &io.0[..]), (steps, &[3][..]));
+
+        assert_eq!(debugger.resume_back(), Stop::Start);
+        assert_eq!((debugger.steps(), debugger.location(), &debugger.tape()[..2]), (0, 0, &[0, 0][..]));
+        assert_eq!(debugger.step_back(), Stop::Start);
+    }
+}
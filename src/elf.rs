@@ -0,0 +1,95 @@
+// A static Linux x86-64 executable: one loadable segment with the headers
+// and code, and one zero-filled segment holding the tape.
+use brainfuck::*;
+use byteorder::{WriteBytesExt, LittleEndian};
+use x86;
+
+const BASE_ADDRESS: u64 = 0x40_0000;
+const PAGE_SIZE: u64 = 0x1000;
+const ELF_HEADER_SIZE: u16 = 64;
+const PROGRAM_HEADER_SIZE: u16 = 56;
+const PROGRAM_HEADERS: u16 = 2;
+
+const PT_LOAD: u32 = 1;
+const PF_X: u32 = 1;
+const PF_W: u32 = 2;
+const PF_R: u32 = 4;
+
+fn program_header(vec: &mut Vec<u8>, flags: u32, vaddr: u64, filesz: u64, memsz: u64) {
+    vec.write_u32::<LittleEndian>(PT_LOAD).unwrap();
+    vec.write_u32::<LittleEndian>(flags).unwrap();
+    vec.write_u64::<LittleEndian>(0).unwrap(); // offset
+    vec.write_u64::<LittleEndian>(vaddr).unwrap();
+    vec.write_u64::<LittleEndian>(vaddr).unwrap(); // physical address
+    vec.write_u64::<LittleEndian>(filesz).unwrap();
+    vec.write_u64::<LittleEndian>(memsz).unwrap();
+    vec.write_u64::<LittleEndian>(PAGE_SIZE).unwrap();
+}
+
+/// `tape_size` must be below 2^31, the largest move an x86 immediate can
+/// hold.
+pub fn to_elf(ops: &[Node], tape_size: usize) -> Vec<u8> {
+    let headers_size = u64::from(ELF_HEADER_SIZE + PROGRAM_HEADERS * PROGRAM_HEADER_SIZE);
+
+    let mut code = vec![];
+    x86::mov_r12_imm64(&mut code, 0); // patched below
+    x86::tape_bounds(&mut code, tape_size as u32);
+    x86::compile(ops, &x86::Runtime::Syscalls { tape_size: tape_size as u32 }, &mut code);
+    x86::exit(&mut code);
+
+    let file_size = headers_size + code.len() as u64;
+    let tape_address = (BASE_ADDRESS + file_size).div_ceil(PAGE_SIZE) * PAGE_SIZE + PAGE_SIZE;
+    (&mut code[2..10]).write_u64::<LittleEndian>(tape_address).unwrap();
+
+    let mut vec = vec![];
+    vec.extend_from_slice(&[0x7f, b'E', b'L', b'F']);
+    vec.extend_from_slice(&[2, 1, 1, 0]); // 64 bit, little endian, version 1, System V
+    vec.extend_from_slice(&[0; 8]);
+    vec.write_u16::<LittleEndian>(2).unwrap(); // executable
+    vec.write_u16::<LittleEndian>(0x3e).unwrap(); // x86-64
+    vec.write_u32::<LittleEndian>(1).unwrap(); // version
+    vec.write_u64::<LittleEndian>(BASE_ADDRESS + headers_size).unwrap(); // entry point
+    vec.write_u64::<LittleEndian>(u64::from(ELF_HEADER_SIZE)).unwrap(); // program headers
+    vec.write_u64::<LittleEndian>(0).unwrap(); // section headers
+    vec.write_u32::<LittleEndian>(0).unwrap(); // flags
+    vec.write_u16::<LittleEndian>(ELF_HEADER_SIZE).unwrap();
+    vec.write_u16::<LittleEndian>(PROGRAM_HEADER_SIZE).unwrap();
+    vec.write_u16::<LittleEndian>(PROGRAM_HEADERS).unwrap();
+    vec.write_u16::<LittleEndian>(0).unwrap(); // section header size
+    vec.write_u16::<LittleEndian>(0).unwrap(); // section headers
+    vec.write_u16::<LittleEndian>(0).unwrap(); // section name table
+
+    program_header(&mut vec, PF_R | PF_X, BASE_ADDRESS, file_size, file_size);
+    program_header(&mut vec, PF_R | PF_W, tape_address, 0, tape_size as u64);
+
+    vec.append(&mut code);
+    vec
+}
+
+#[cfg(all(test, target_arch = "x86_64", target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::process::Command;
+
+    fn run(code: &str, tape_size: usize) -> Vec<u8> {
+        let (ast, _) = get_ast(&code.chars().collect::<Vec<_>>());
+        let path = std::env::temp_dir().join(format!("bf-elf-test-{}-{}", std::process::id(), tape_size));
+        fs::write(&path, to_elf(&compact(&ast), tape_size)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let output = Command::new(&path).output().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(output.status.success());
+        output.stdout
+    }
+
+    #[test]
+    fn pointer_wraps_around_both_ends_of_the_tape() {
+        // the loop runs on the last cell and counts up the first one, which
+        // a tape of 5 cells reaches again 5 cells to the left
+        let code = "<+++++++[>++++++++++<-]>+++.<<<<<.";
+        assert_eq!(run(code, HEAP_SIZE), b"I\0");
+        assert_eq!(run(code, 5), b"II");
+    }
+}
@@ -0,0 +1,71 @@
+use brainfuck::*;
+use std::num::Wrapping;
+
+/// How a running program talks to its host.
+pub trait Io {
+    fn print(&mut self, val: u8);
+    fn read(&mut self) -> u8;
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.print(*b);
+        }
+    }
+}
+
+struct State<'a> {
+    curr_ptr: usize,
+    data: [u8; HEAP_SIZE],
+    io: &'a mut dyn Io,
+}
+
+
+fn eval_while(state: &mut State, ops: &[Node]) {
+    while state.data[state.curr_ptr] != 0 {
+        eval_vec(state, ops);
+    }
+}
+
+fn eval_vec(state: &mut State, ops: &[Node]) {
+    for node in ops {
+        eval(state, &node.op);
+    }
+}
+
+fn eval(state: &mut State, op: &Op) {
+    match *op {
+        Op::IncPointer(n) => {
+            state.curr_ptr = (state.curr_ptr + n % HEAP_SIZE) % HEAP_SIZE
+        }
+        Op::DecPointer(n) => {
+            state.curr_ptr = (state.curr_ptr + HEAP_SIZE - n % HEAP_SIZE) % HEAP_SIZE
+        }
+        Op::While { ref ops } => eval_while(state, ops),
+        Op::IncVal(n) => {
+            state.data[state.curr_ptr] = (Wrapping(state.data[state.curr_ptr]) + Wrapping(n)).0
+        }
+        Op::DecVal(n) => {
+            state.data[state.curr_ptr] = (Wrapping(state.data[state.curr_ptr]) - Wrapping(n)).0
+        }
+        Op::SetRegisterToZero => state.data[state.curr_ptr] = 0,
+
+        Op::Print => state.io.print(state.data[state.curr_ptr]),
+        Op::Read => state.data[state.curr_ptr] = state.io.read(),
+        Op::InitTape { ref data, ptr } => {
+            state.data[..data.len()].copy_from_slice(data);
+            state.curr_ptr = ptr;
+        }
+        Op::PrintBytes(ref bytes) => state.io.write(bytes),
+    }
+}
+
+/// Runs the program on a zeroed tape; the pointer wraps around at both
+/// ends of the tape.
+pub fn run(ops: &[Node], io: &mut dyn Io) {
+    let mut state = State {
+        curr_ptr: 0,
+        data: [0; HEAP_SIZE],
+        io,
+    };
+    eval_vec(&mut state, ops);
+}
@@ -0,0 +1,100 @@
+// Compiles the `Op` tree to x86-64 code in executable memory and runs it
+// in-process. Other hosts fall back to the bytecode interpreter.
+use brainfuck::*;
+use interpreter::Io;
+use vm;
+
+#[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+mod native {
+    use brainfuck::*;
+    use interpreter::Io;
+    use std::mem;
+    use std::os::raw::{c_int, c_long, c_void};
+    use std::ptr;
+    use std::slice;
+    use x86;
+
+    const PROT_READ: c_int = 1;
+    const PROT_WRITE: c_int = 2;
+    const PROT_EXEC: c_int = 4;
+    const MAP_PRIVATE: c_int = 2;
+    const MAP_ANONYMOUS: c_int = 0x20;
+
+    extern "C" {
+        fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: c_int, offset: c_long) -> *mut c_void;
+        fn mprotect(addr: *mut c_void, len: usize, prot: c_int) -> c_int;
+        fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    }
+
+    type Context<'a> = &'a mut dyn Io;
+
+    extern "C" fn print(ctx: *mut Context, val: u32) {
+        unsafe { (*ctx).print(val as u8) }
+    }
+
+    extern "C" fn read(ctx: *mut Context) -> u32 {
+        u32::from(unsafe { (*ctx).read() })
+    }
+
+    extern "C" fn write(ctx: *mut Context, bytes: *const u8, len: usize) {
+        unsafe { (*ctx).write(slice::from_raw_parts(bytes, len)) }
+    }
+
+    // extern "C" fn(tape: *mut u8, ctx: *mut Context)
+    fn function(ops: &[Node]) -> Vec<u8> {
+        let runtime = x86::Runtime::Callbacks {
+            print: print as *const () as u64,
+            read: read as *const () as u64,
+            write: write as *const () as u64,
+            tape_size: HEAP_SIZE as u32,
+        };
+        let mut code = vec![];
+        code.extend_from_slice(&[0x53, 0x41, 0x54, 0x41, 0x55, 0x41, 0x56]); // push rbx, r12, r13, r14
+        code.extend_from_slice(&[0x48, 0x83, 0xec, 0x08]); // sub rsp, 8 (keeps calls 16 byte aligned)
+        code.extend_from_slice(&[0x49, 0x89, 0xfc]); // mov r12, rdi
+        code.extend_from_slice(&[0x49, 0x89, 0xf5]); // mov r13, rsi
+        x86::tape_bounds(&mut code, HEAP_SIZE as u32);
+        x86::compile(ops, &runtime, &mut code);
+        code.extend_from_slice(&[0x48, 0x83, 0xc4, 0x08]); // add rsp, 8
+        code.extend_from_slice(&[0x41, 0x5e, 0x41, 0x5d, 0x41, 0x5c, 0x5b]); // pop r14, r13, r12, rbx
+        code.push(0xc3); // ret
+        code
+    }
+
+    /// Returns `false` when executable memory is not available.
+    pub fn run(ops: &[Node], io: &mut dyn Io) -> bool {
+        let code = function(ops);
+        unsafe {
+            let memory = mmap(ptr::null_mut(), code.len(), PROT_READ | PROT_WRITE,
+                              MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+            if memory as isize == -1 {
+                return false;
+            }
+            ptr::copy_nonoverlapping(code.as_ptr(), memory as *mut u8, code.len());
+            if mprotect(memory, code.len(), PROT_READ | PROT_EXEC) != 0 {
+                munmap(memory, code.len());
+                return false;
+            }
+
+            let f: extern "C" fn(*mut u8, *mut Context) = mem::transmute(memory);
+            let mut tape = vec![0u8; HEAP_SIZE];
+            let mut ctx: Context = io;
+            f(tape.as_mut_ptr(), &mut ctx);
+
+            munmap(memory, code.len());
+        }
+        true
+    }
+}
+
+/// Same behaviour as `interpreter::run`, compiled to native code where
+/// the host supports it.
+pub fn run(ops: &[Node], io: &mut dyn Io) {
+    #[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+    {
+        if native::run(ops, io) {
+            return;
+        }
+    }
+    vm::run(&vm::compile(ops), io)
+}
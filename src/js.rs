@@ -0,0 +1,44 @@
+use brainfuck::*;
+use std::fmt::Write;
+
+fn to_js_block(ops: &[Node], indent: usize, out: &mut String) {
+    let pad = "  ".repeat(indent);
+    for node in ops {
+        match node.op {
+            Op::IncPointer(n) => writeln!(out, "{}p = (p + {}) % TAPE_SIZE;", pad, n % HEAP_SIZE).unwrap(),
+            Op::DecPointer(n) => {
+                writeln!(out, "{}p = (p + TAPE_SIZE - {}) % TAPE_SIZE;", pad, n % HEAP_SIZE).unwrap()
+            }
+            Op::IncVal(n) => writeln!(out, "{}tape[p] += {};", pad, n).unwrap(),
+            Op::DecVal(n) => writeln!(out, "{}tape[p] -= {};", pad, n).unwrap(),
+            Op::SetRegisterToZero => writeln!(out, "{}tape[p] = 0;", pad).unwrap(),
+            Op::Print => writeln!(out, "{}io.print(tape[p]);", pad).unwrap(),
+            Op::Read => writeln!(out, "{}tape[p] = io.read();", pad).unwrap(),
+            Op::InitTape { ref data, ptr } => {
+                writeln!(out, "{}tape.set({:?});", pad, data).unwrap();
+                writeln!(out, "{}p = {};", pad, ptr).unwrap();
+            }
+            Op::PrintBytes(ref bytes) => {
+                writeln!(out, "{}for (const v of {:?}) io.print(v);", pad, bytes).unwrap()
+            }
+            Op::While { ref ops } => {
+                writeln!(out, "{}while (tape[p] !== 0) {{", pad).unwrap();
+                to_js_block(ops, indent + 1, out);
+                writeln!(out, "{}}}", pad).unwrap();
+            }
+        }
+    }
+}
+
+/// Translates the program to a JavaScript function expression taking the
+/// same `print(v)` and `read()` hooks as the `io` imports of the wasm
+/// module, e.g. `eval(source)({ print, read })`.
+pub fn to_js(ops: &[Node]) -> String {
+    let mut out = String::new();
+    out.push_str("(function (io) {\n");
+    writeln!(out, "  const TAPE_SIZE = {};", HEAP_SIZE).unwrap();
+    out.push_str("  const tape = new Uint8Array(TAPE_SIZE);\n  let p = 0;\n");
+    to_js_block(ops, 1, &mut out);
+    out.push_str("})\n");
+    out
+}
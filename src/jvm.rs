@@ -0,0 +1,331 @@
+// A class file with a single `public static void main(String[])`. The
+// class version predates StackMapTable, so branches need no frame data.
+use brainfuck::*;
+use byteorder::{WriteBytesExt, BigEndian};
+use std::collections::HashMap;
+
+const CLASS_MAGIC: u32 = 0xCAFE_BABE;
+const CLASS_VERSION: u16 = 49;
+
+const ACC_PUBLIC: u16 = 0x0001;
+const ACC_STATIC: u16 = 0x0008;
+const ACC_SUPER: u16 = 0x0020;
+
+const CONSTANT_UTF8: u8 = 1;
+const CONSTANT_INTEGER: u8 = 3;
+const CONSTANT_CLASS: u8 = 7;
+const CONSTANT_STRING: u8 = 8;
+const CONSTANT_FIELDREF: u8 = 9;
+const CONSTANT_METHODREF: u8 = 10;
+const CONSTANT_NAME_AND_TYPE: u8 = 12;
+
+const ICONST_0: u8 = 0x03;
+const BIPUSH: u8 = 0x10;
+const SIPUSH: u8 = 0x11;
+const LDC: u8 = 0x12;
+const LDC_W: u8 = 0x13;
+const ALOAD: u8 = 0x19;
+const ILOAD_2: u8 = 0x1c;
+const ALOAD_1: u8 = 0x2b;
+const ALOAD_3: u8 = 0x2d;
+const BALOAD: u8 = 0x33;
+const ASTORE: u8 = 0x3a;
+const ISTORE_2: u8 = 0x3d;
+const ASTORE_1: u8 = 0x4c;
+const ASTORE_3: u8 = 0x4e;
+const BASTORE: u8 = 0x54;
+const DUP: u8 = 0x59;
+const DUP2: u8 = 0x5c;
+const IADD: u8 = 0x60;
+const IREM: u8 = 0x70;
+const ISHR: u8 = 0x7a;
+const IAND: u8 = 0x7e;
+const IXOR: u8 = 0x82;
+const I2B: u8 = 0x91;
+const IFNE: u8 = 0x9a;
+const RETURN: u8 = 0xb1;
+const GETSTATIC: u8 = 0xb2;
+const INVOKEVIRTUAL: u8 = 0xb6;
+const NEWARRAY: u8 = 0xbc;
+const GOTO_W: u8 = 0xc8;
+const T_BYTE: u8 = 8;
+
+// Locals of `main`: 0 is the argument array.
+const STDIN: u8 = 4;
+const MAX_STACK: u16 = 6;
+const MAX_LOCALS: u16 = 5;
+
+// Longest literal printed by one `ldc`, well within the 65535 byte limit
+// of an encoded constant.
+const STRING_CHUNK: usize = 16384;
+
+#[derive(Default)]
+struct ConstantPool {
+    bytes: Vec<u8>,
+    indices: HashMap<Vec<u8>, u16>,
+    next: u16,
+}
+
+impl ConstantPool {
+    fn add(&mut self, entry: Vec<u8>) -> u16 {
+        if let Some(&index) = self.indices.get(&entry) {
+            return index;
+        }
+        self.next += 1;
+        self.bytes.extend_from_slice(&entry);
+        self.indices.insert(entry, self.next);
+        self.next
+    }
+
+    fn utf8(&mut self, bytes: &[u8]) -> u16 {
+        let mut entry = vec![CONSTANT_UTF8];
+        entry.write_u16::<BigEndian>(bytes.len() as u16).unwrap();
+        entry.extend_from_slice(bytes);
+        self.add(entry)
+    }
+
+    fn with_indices(&mut self, tag: u8, indices: &[u16]) -> u16 {
+        let mut entry = vec![tag];
+        for &index in indices {
+            entry.write_u16::<BigEndian>(index).unwrap();
+        }
+        self.add(entry)
+    }
+
+    fn integer(&mut self, val: i32) -> u16 {
+        let mut entry = vec![CONSTANT_INTEGER];
+        entry.write_i32::<BigEndian>(val).unwrap();
+        self.add(entry)
+    }
+
+    fn class(&mut self, name: &str) -> u16 {
+        let name = self.utf8(name.as_bytes());
+        self.with_indices(CONSTANT_CLASS, &[name])
+    }
+
+    // The bytes become the chars U+0000..U+00FF, so `getBytes` with
+    // ISO-8859-1 gives them back.
+    fn latin1_string(&mut self, bytes: &[u8]) -> u16 {
+        let utf8 = modified_utf8(bytes);
+        let utf8 = self.utf8(&utf8);
+        self.with_indices(CONSTANT_STRING, &[utf8])
+    }
+
+    fn member(&mut self, tag: u8, class: &str, name: &str, descriptor: &str) -> u16 {
+        let class = self.class(class);
+        let name = self.utf8(name.as_bytes());
+        let descriptor = self.utf8(descriptor.as_bytes());
+        let name_and_type = self.with_indices(CONSTANT_NAME_AND_TYPE, &[name, descriptor]);
+        self.with_indices(tag, &[class, name_and_type])
+    }
+}
+
+fn modified_utf8(bytes: &[u8]) -> Vec<u8> {
+    let mut res = Vec::with_capacity(bytes.len());
+    for &b in bytes {
+        match b {
+            0x01..=0x7f => res.push(b),
+            _ => {
+                res.push(0xc0 | (b >> 6));
+                res.push(0x80 | (b & 0x3f));
+            }
+        }
+    }
+    res
+}
+
+struct Method<'a> {
+    pool: &'a mut ConstantPool,
+    code: Vec<u8>,
+    write: u16,
+    write_bytes: u16,
+    flush: u16,
+    read: u16,
+    get_bytes: u16,
+    latin1: u16,
+}
+
+impl<'a> Method<'a> {
+    fn push_int(&mut self, val: i32) {
+        match val {
+            -1..=5 => self.code.push((i32::from(ICONST_0) + val) as u8),
+            -128..=127 => {
+                self.code.push(BIPUSH);
+                self.code.push(val as u8);
+            }
+            -32768..=32767 => {
+                self.code.push(SIPUSH);
+                self.code.write_i16::<BigEndian>(val as i16).unwrap();
+            }
+            _ => {
+                let index = self.pool.integer(val);
+                self.ldc(index);
+            }
+        }
+    }
+
+    fn ldc(&mut self, index: u16) {
+        if index < 256 {
+            self.code.push(LDC);
+            self.code.push(index as u8);
+        } else {
+            self.code.push(LDC_W);
+            self.code.write_u16::<BigEndian>(index).unwrap();
+        }
+    }
+
+    fn with_index(&mut self, opcode: u8, index: u16) {
+        self.code.push(opcode);
+        self.code.write_u16::<BigEndian>(index).unwrap();
+    }
+
+    fn goto_w(&mut self, target: usize) {
+        let offset = target as i32 - self.code.len() as i32;
+        self.code.push(GOTO_W);
+        self.code.write_i32::<BigEndian>(offset).unwrap();
+    }
+
+    fn move_pointer(&mut self, step: usize) {
+        self.code.push(ILOAD_2);
+        self.push_int(step as i32);
+        self.code.push(IADD);
+        self.push_int(HEAP_SIZE as i32);
+        self.code.push(IREM);
+        self.code.push(ISTORE_2);
+    }
+
+    fn add(&mut self, n: u8) {
+        self.code.extend_from_slice(&[ALOAD_1, ILOAD_2, DUP2, BALOAD]);
+        self.push_int(i32::from(n as i8));
+        self.code.extend_from_slice(&[IADD, I2B, BASTORE]);
+    }
+
+    fn block(&mut self, ops: &[Node]) {
+        for node in ops {
+            match node.op {
+                Op::IncPointer(n) => self.move_pointer(n % HEAP_SIZE),
+                Op::DecPointer(n) => self.move_pointer(HEAP_SIZE - n % HEAP_SIZE),
+                Op::IncVal(n) => self.add(n),
+                Op::DecVal(n) => self.add(n.wrapping_neg()),
+                Op::SetRegisterToZero => self.code.extend_from_slice(&[ALOAD_1, ILOAD_2, ICONST_0, BASTORE]),
+                Op::Print => {
+                    self.code.extend_from_slice(&[ALOAD_3, ALOAD_1, ILOAD_2, BALOAD]);
+                    let write = self.write;
+                    self.with_index(INVOKEVIRTUAL, write);
+                }
+                Op::Read => {
+                    let (flush, read) = (self.flush, self.read);
+                    self.code.push(ALOAD_3);
+                    self.with_index(INVOKEVIRTUAL, flush);
+                    self.code.extend_from_slice(&[ALOAD_1, ILOAD_2, ALOAD, STDIN]);
+                    self.with_index(INVOKEVIRTUAL, read);
+                    // EOF (-1) becomes 0: v & ~(v >> 31)
+                    self.code.extend_from_slice(&[DUP, BIPUSH, 31, ISHR]);
+                    self.push_int(-1);
+                    self.code.extend_from_slice(&[IXOR, IAND, I2B, BASTORE]);
+                }
+                Op::InitTape { ref data, ptr } => {
+                    for (i, &b) in data.iter().enumerate().filter(|&(_, &b)| b != 0) {
+                        self.code.push(ALOAD_1);
+                        self.push_int(i as i32);
+                        self.push_int(i32::from(b as i8));
+                        self.code.push(BASTORE);
+                    }
+                    self.push_int(ptr as i32);
+                    self.code.push(ISTORE_2);
+                }
+                Op::PrintBytes(ref bytes) => {
+                    for chunk in bytes.chunks(STRING_CHUNK) {
+                        let (string, latin1) = (self.pool.latin1_string(chunk), self.latin1);
+                        let (get_bytes, write_bytes) = (self.get_bytes, self.write_bytes);
+                        self.code.push(ALOAD_3);
+                        self.ldc(string);
+                        self.ldc(latin1);
+                        self.with_index(INVOKEVIRTUAL, get_bytes);
+                        self.with_index(INVOKEVIRTUAL, write_bytes);
+                    }
+                }
+                Op::While { ref ops } => {
+                    // goto_w keeps jumps valid in methods beyond 32 KiB
+                    let start = self.code.len();
+                    self.code.extend_from_slice(&[ALOAD_1, ILOAD_2, BALOAD, IFNE, 0, 8]);
+                    let exit = self.code.len();
+                    self.goto_w(0); // patched below
+                    self.block(ops);
+                    self.goto_w(start);
+                    let offset = (self.code.len() - exit) as i32;
+                    (&mut self.code[exit + 1..exit + 5]).write_i32::<BigEndian>(offset).unwrap();
+                }
+            }
+        }
+    }
+}
+
+/// Writes a class file named `class_name` whose `main` runs the program on
+/// `System.in` and `System.out`. Fails for programs whose bytecode does not
+/// fit in the 64KB the JVM allows a method.
+pub fn to_class(ops: &[Node], class_name: &str) -> Result<Vec<u8>, String> {
+    let mut pool = ConstantPool::default();
+    let this_class = pool.class(class_name);
+    let super_class = pool.class("java/lang/Object");
+    let main_name = pool.utf8(b"main");
+    let main_descriptor = pool.utf8(b"([Ljava/lang/String;)V");
+    let code_name = pool.utf8(b"Code");
+    let out = pool.member(CONSTANT_FIELDREF, "java/lang/System", "out", "Ljava/io/PrintStream;");
+    let stdin = pool.member(CONSTANT_FIELDREF, "java/lang/System", "in", "Ljava/io/InputStream;");
+
+    let mut method = Method {
+        write: pool.member(CONSTANT_METHODREF, "java/io/PrintStream", "write", "(I)V"),
+        write_bytes: pool.member(CONSTANT_METHODREF, "java/io/PrintStream", "write", "([B)V"),
+        flush: pool.member(CONSTANT_METHODREF, "java/io/PrintStream", "flush", "()V"),
+        read: pool.member(CONSTANT_METHODREF, "java/io/InputStream", "read", "()I"),
+        get_bytes: pool.member(CONSTANT_METHODREF, "java/lang/String", "getBytes", "(Ljava/lang/String;)[B"),
+        latin1: pool.latin1_string(b"ISO-8859-1"),
+        pool: &mut pool,
+        code: vec![],
+    };
+    method.push_int(HEAP_SIZE as i32);
+    method.code.extend_from_slice(&[NEWARRAY, T_BYTE, ASTORE_1, ICONST_0, ISTORE_2]);
+    method.with_index(GETSTATIC, out);
+    method.code.push(ASTORE_3);
+    method.with_index(GETSTATIC, stdin);
+    method.code.extend_from_slice(&[ASTORE, STDIN]);
+    method.block(ops);
+    method.code.push(ALOAD_3);
+    let flush = method.flush;
+    method.with_index(INVOKEVIRTUAL, flush);
+    method.code.push(RETURN);
+    let code = method.code;
+    if code.len() >= 65536 {
+        return Err(format!("the program needs {} bytes of JVM bytecode, more than a method can hold", code.len()));
+    }
+
+    let mut vec = vec![];
+    vec.write_u32::<BigEndian>(CLASS_MAGIC).unwrap();
+    vec.write_u16::<BigEndian>(0).unwrap(); // minor version
+    vec.write_u16::<BigEndian>(CLASS_VERSION).unwrap();
+    vec.write_u16::<BigEndian>(pool.next + 1).unwrap();
+    vec.extend_from_slice(&pool.bytes);
+    vec.write_u16::<BigEndian>(ACC_PUBLIC | ACC_SUPER).unwrap();
+    vec.write_u16::<BigEndian>(this_class).unwrap();
+    vec.write_u16::<BigEndian>(super_class).unwrap();
+    vec.write_u16::<BigEndian>(0).unwrap(); // interfaces
+    vec.write_u16::<BigEndian>(0).unwrap(); // fields
+
+    vec.write_u16::<BigEndian>(1).unwrap(); // methods
+    vec.write_u16::<BigEndian>(ACC_PUBLIC | ACC_STATIC).unwrap();
+    vec.write_u16::<BigEndian>(main_name).unwrap();
+    vec.write_u16::<BigEndian>(main_descriptor).unwrap();
+    vec.write_u16::<BigEndian>(1).unwrap(); // attributes
+    vec.write_u16::<BigEndian>(code_name).unwrap();
+    vec.write_u32::<BigEndian>(12 + code.len() as u32).unwrap();
+    vec.write_u16::<BigEndian>(MAX_STACK).unwrap();
+    vec.write_u16::<BigEndian>(MAX_LOCALS).unwrap();
+    vec.write_u32::<BigEndian>(code.len() as u32).unwrap();
+    vec.extend_from_slice(&code);
+    vec.write_u16::<BigEndian>(0).unwrap(); // exception table
+    vec.write_u16::<BigEndian>(0).unwrap(); // code attributes
+
+    vec.write_u16::<BigEndian>(0).unwrap(); // class attributes
+    Ok(vec)
+}
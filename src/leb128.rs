@@ -17,3 +17,22 @@ pub fn encode_unsigned(val:u32) -> Vec<u8> {
     }
 }
 
+
+pub fn encode_signed(val:i32) -> Vec<u8> {
+    let mut val = val;
+    let mut result = vec![];
+    loop {
+        let mut byte = val as u8 & 0b0111_1111;
+        val >>= 7;
+        let done = (val == 0 && byte & 0b0100_0000 == 0) ||
+                   (val == -1 && byte & 0b0100_0000 != 0);
+        if !done {
+            byte |= 0b1000_0000;
+        }
+        result.push(byte);
+
+        if done {
+            return result;
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// Textual LLVM IR. The pointer is kept in an alloca and every op reloads
+// it, leaving register allocation to LLVM's mem2reg. Pointers are written
+// typed (`i8*`), which newer releases still read as opaque `ptr`.
+use brainfuck::*;
+use std::fmt::Write;
+
+struct Emitter {
+    globals: String,
+    body: String,
+    next: usize,
+}
+
+impl Emitter {
+    fn tmp(&mut self) -> String {
+        self.next += 1;
+        format!("%t{}", self.next)
+    }
+
+    fn line(&mut self, line: &str) {
+        self.body.push_str("  ");
+        self.body.push_str(line);
+        self.body.push('\n');
+    }
+
+    // Returns the address of the current cell.
+    fn cell(&mut self) -> String {
+        let p = self.tmp();
+        let cell = self.tmp();
+        self.line(&format!("{} = load i64, i64* %p", p));
+        self.line(&format!("{} = getelementptr inbounds [{n} x i8], [{n} x i8]* @tape, i64 0, i64 {}",
+                           cell, p, n = HEAP_SIZE));
+        cell
+    }
+
+    fn move_pointer(&mut self, step: usize) {
+        let p = self.tmp();
+        let sum = self.tmp();
+        let wrapped = self.tmp();
+        self.line(&format!("{} = load i64, i64* %p", p));
+        self.line(&format!("{} = add i64 {}, {}", sum, p, step));
+        self.line(&format!("{} = urem i64 {}, {}", wrapped, sum, HEAP_SIZE));
+        self.line(&format!("store i64 {}, i64* %p", wrapped));
+    }
+
+    fn add(&mut self, n: u8) {
+        let cell = self.cell();
+        let val = self.tmp();
+        let sum = self.tmp();
+        self.line(&format!("{} = load i8, i8* {}", val, cell));
+        self.line(&format!("{} = add i8 {}, {}", sum, val, n));
+        self.line(&format!("store i8 {}, i8* {}", sum, cell));
+    }
+
+    fn putchar(&mut self, val: &str) {
+        self.line(&format!("call i32 @putchar(i32 {})", val));
+    }
+
+    fn constant(&mut self, bytes: &[u8]) -> String {
+        self.next += 1;
+        let name = format!("@const{}", self.next);
+        let mut literal = String::new();
+        for b in bytes {
+            match *b {
+                b'"' | b'\\' => write!(literal, "\\{:02X}", b).unwrap(),
+                b' '..=b'~' => literal.push(*b as char),
+                b => write!(literal, "\\{:02X}", b).unwrap(),
+            }
+        }
+        writeln!(self.globals, "{} = private unnamed_addr constant [{} x i8] c\"{}\"",
+                 name, bytes.len(), literal).unwrap();
+        name
+    }
+
+    fn block(&mut self, ops: &[Node]) {
+        for node in ops {
+            match node.op {
+                Op::IncPointer(n) => self.move_pointer(n % HEAP_SIZE),
+                Op::DecPointer(n) => self.move_pointer(HEAP_SIZE - n % HEAP_SIZE),
+                Op::IncVal(n) => self.add(n),
+                Op::DecVal(n) => self.add(n.wrapping_neg()),
+                Op::SetRegisterToZero => {
+                    let cell = self.cell();
+                    self.line(&format!("store i8 0, i8* {}", cell));
+                }
+                Op::Print => {
+                    let cell = self.cell();
+                    let val = self.tmp();
+                    let ext = self.tmp();
+                    self.line(&format!("{} = load i8, i8* {}", val, cell));
+                    self.line(&format!("{} = zext i8 {} to i32", ext, val));
+                    self.putchar(&ext);
+                }
+                Op::Read => {
+                    let c = self.tmp();
+                    let eof = self.tmp();
+                    let val = self.tmp();
+                    let byte = self.tmp();
+                    self.line(&format!("{} = call i32 @getchar()", c));
+                    self.line(&format!("{} = icmp eq i32 {}, -1", eof, c));
+                    self.line(&format!("{} = select i1 {}, i32 0, i32 {}", val, eof, c));
+                    self.line(&format!("{} = trunc i32 {} to i8", byte, val));
+                    let cell = self.cell();
+                    self.line(&format!("store i8 {}, i8* {}", byte, cell));
+                }
+                Op::InitTape { ref data, ptr } => {
+                    let init = self.constant(data);
+                    self.line(&format!("call i8* @memcpy(i8* getelementptr inbounds ([{n} x i8], [{n} x i8]* @tape, i64 0, i64 0), \
+                                        i8* getelementptr inbounds ([{len} x i8], [{len} x i8]* {init}, i64 0, i64 0), i64 {len})",
+                                       n = HEAP_SIZE, len = data.len(), init = init));
+                    self.line(&format!("store i64 {}, i64* %p", ptr));
+                }
+                Op::PrintBytes(ref bytes) => {
+                    for b in bytes {
+                        self.putchar(&b.to_string());
+                    }
+                }
+                Op::While { ref ops } => {
+                    self.next += 1;
+                    let id = self.next;
+                    self.line(&format!("br label %loop{}", id));
+                    self.body.push_str(&format!("loop{}:\n", id));
+                    let cell = self.cell();
+                    let val = self.tmp();
+                    let nonzero = self.tmp();
+                    self.line(&format!("{} = load i8, i8* {}", val, cell));
+                    self.line(&format!("{} = icmp ne i8 {}, 0", nonzero, val));
+                    self.line(&format!("br i1 {}, label %body{}, label %end{}", nonzero, id, id));
+                    self.body.push_str(&format!("body{}:\n", id));
+                    self.block(ops);
+                    self.line(&format!("br label %loop{}", id));
+                    self.body.push_str(&format!("end{}:\n", id));
+                }
+            }
+        }
+    }
+}
+
+/// Translates the program to an LLVM IR module whose `main` runs it with
+/// the interpreter's semantics.
+pub fn to_llvm(ops: &[Node]) -> String {
+    let mut emitter = Emitter {
+        globals: String::new(),
+        body: String::new(),
+        next: 0,
+    };
+    emitter.line("%p = alloca i64");
+    emitter.line("store i64 0, i64* %p");
+    emitter.block(ops);
+    emitter.line("ret i32 0");
+
+    let mut out = String::new();
+    writeln!(out, "@tape = internal global [{} x i8] zeroinitializer", HEAP_SIZE).unwrap();
+    out.push_str(&emitter.globals);
+    out.push_str("\ndeclare i32 @putchar(i32)\n");
+    out.push_str("declare i32 @getchar()\n");
+    out.push_str("declare i8* @memcpy(i8*, i8*, i64)\n\n");
+    out.push_str("define i32 @main() {\nentry:\n");
+    out.push_str(&emitter.body);
+    out.push_str("}\n");
+    out
+}
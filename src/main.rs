@@ -1,12 +1,37 @@
 mod brainfuck;
+mod c;
+mod cli;
+mod coverage;
+mod debugger;
+mod elf;
+mod interpreter;
+mod jit;
+mod js;
+mod jvm;
 mod wasm;
+mod x86;
 mod leb128;
+mod llvm;
+mod json;
+mod source_map;
+mod trace;
+mod partial_eval;
+mod passes;
+mod profile;
+mod run_result;
+mod rust;
+mod session;
+mod snapshot;
+mod vm;
+mod web;
 
 use brainfuck::*;
+use interpreter::Io;
+use passes::{PassManager, PassStats};
+use run_result::RunResult;
 use std::ffi::CStr;
 use std::ffi::CString;
 use std::os::raw::{c_char};
-use std::num::Wrapping;
 extern crate byteorder;
 
 
@@ -17,125 +42,807 @@ use std::mem;
 #[repr(C)]
 #[derive(Debug)]
 pub struct JsBytes {
-    ptr: u32,
-    len: u32,
-    cap: u32,
+    ptr: usize,
+    len: usize,
+    cap: usize,
 }
 
 impl JsBytes {
-    pub fn new(mut bytes: Vec<u8>) -> *mut JsBytes {
-        let ptr = bytes.as_mut_ptr() as u32;
-        let len = bytes.len() as u32;
-        let cap = bytes.capacity() as u32;
+    pub fn new(bytes: Vec<u8>) -> *mut JsBytes {
+        Box::into_raw(Box::new(JsBytes::from_vec(bytes)))
+    }
+
+    fn from_vec(mut bytes: Vec<u8>) -> JsBytes {
+        let ptr = bytes.as_mut_ptr() as usize;
+        let len = bytes.len();
+        let cap = bytes.capacity();
         mem::forget(bytes);
-        let boxed = Box::new(JsBytes { ptr, len, cap });
-        Box::into_raw(boxed)
+        JsBytes { ptr, len, cap }
+    }
+
+    // Safety: `self` must come from `from_vec`.
+    unsafe fn into_vec(self) -> Vec<u8> {
+        Vec::from_raw_parts(self.ptr as *mut u8, self.len, self.cap)
     }
 }
 
+// Every pointer an export returns is owned by the caller, who hands it back
+// to the matching function below: `free_string` for strings, `drop_bytes`
+// for `JsBytes`, `drop_run_output` and `session_free` for the rest. Buffers
+// the host fills itself come from `alloc_bytes`.
+
+/// # Safety
+///
+/// `ptr` must be a string returned by an export and must not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe fn free_string(ptr: *mut c_char) {
+    drop(CString::from_raw(ptr));
+}
+
+/// `len` zeroed bytes for the host to fill, to be freed with `free_bytes`.
+#[no_mangle]
+pub fn alloc_bytes(len: usize) -> *mut u8 {
+    Box::into_raw(vec![0u8; len].into_boxed_slice()) as *mut u8
+}
+
+/// # Safety
+///
+/// `ptr` must come from `alloc_bytes(len)` and must not be used afterwards.
+#[no_mangle]
+pub unsafe fn free_bytes(ptr: *mut u8, len: usize) {
+    drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)));
+}
+
 
+/// # Safety
+///
+/// `ptr` must come from `JsBytes::new` and must not be used afterwards.
 #[no_mangle]
 pub unsafe fn drop_bytes(ptr: *mut JsBytes) {
-    let boxed: Box<JsBytes> = Box::from_raw(ptr);
-    Vec::from_raw_parts(boxed.ptr as *mut u8, boxed.len as usize, boxed.cap as usize);
+    Box::from_raw(ptr).into_vec();
 }
 
 extern "C" {
-    pub fn read_val(_: *mut c_char) -> u8;
+    /// Gets the output since the previous call, borrowed for the duration
+    /// of the call, and returns the next input byte.
+    pub fn read_val(output: *const u8, len: usize) -> u8;
 }
 
-fn read(current_output: &[u8]) -> u8 {
-    let current_output = String::from_utf8_lossy(current_output).into_owned();
-    unsafe { read_val(to_c_str(&current_output)) }
+// Holds back the output until the page asks for input.
+struct JsIo {
+    pending: Vec<u8>,
 }
 
+impl Io for JsIo {
+    fn print(&mut self, val: u8) {
+        self.pending.push(val);
+    }
 
+    fn read(&mut self) -> u8 {
+        let val = unsafe { read_val(self.pending.as_ptr(), self.pending.len()) };
+        self.pending.clear();
+        val
+    }
+}
 
-const HEAP_SIZE: usize = 4092;
+fn optimise(code: &str, passes: &PassManager) -> (Vec<Node>, Vec<PassStats>) {
+    let chars: Vec<char> = code.chars().collect();
+    let (ast, _) = get_ast(&chars);
+    passes.run(&ast)
+}
 
-struct State {
-    curr_ptr: usize,
-    data: [u8; HEAP_SIZE],
-    output: Vec<u8>,
+// Exports that return a pointer return null when the program or the flags
+// are invalid, so that bad input can't abort the host.
+fn with_program<T, F: FnOnce(String, PassManager) -> *mut T>(code: *mut c_char, flags: &str, f: F) -> *mut T {
+    let code = from_c_str(code);
+    match check(&code).and_then(|_| PassManager::from_flags(flags)) {
+        Ok(passes) => f(code, passes),
+        Err(_) => std::ptr::null_mut(),
+    }
 }
 
+// Like `with_program`, for the backends, which recurse once per loop.
+fn with_backend<T, F: FnOnce(String, PassManager) -> *mut T>(code: *mut c_char, flags: &str, f: F) -> *mut T {
+    with_program(code, flags, |code, passes| match check_nesting(&code.chars().collect::<Vec<_>>()) {
+        Ok(()) => f(code, passes),
+        Err(_) => std::ptr::null_mut(),
+    })
+}
 
-fn eval_while(state: &mut State, ops: &[Op]) {
-    while state.data[state.curr_ptr] != 0 {
-        eval_vec(state, ops);
+// Like `with_backend`, for coverage, which reports on the program as
+// written.
+fn with_coverage<T, F: FnOnce(String, PassManager) -> *mut T>(code: *mut c_char, flags: *mut c_char, f: F) -> *mut T {
+    match PassManager::for_source(&from_c_str(flags), &[]) {
+        Ok(passes) => with_backend(code, "", |code, _| f(code, passes)),
+        Err(_) => std::ptr::null_mut(),
     }
 }
 
-fn eval_vec(state: &mut State, ops: &[Op]) {
-    for op in ops {
-        eval(state, op);
-    }
+fn check(code: &str) -> Result<(), String> {
+    check_brackets(&code.chars().collect::<Vec<_>>())
 }
 
-fn eval(state: &mut State, op: &Op) {
-    match *op {
-        Op::IncPointer(n) => {
-            state.curr_ptr = (Wrapping(state.curr_ptr) + Wrapping(n)).0 % HEAP_SIZE
-        }
-        Op::DecPointer(n) => {
-            state.curr_ptr = (Wrapping(state.curr_ptr) - Wrapping(n)).0 % HEAP_SIZE
-        }
-        Op::While { ref ops } => eval_while(state, ops),
-        Op::IncVal(n) => {
-            state.data[state.curr_ptr] = (Wrapping(state.data[state.curr_ptr]) + Wrapping(n)).0
-        }
-        Op::DecVal(n) => {
-            state.data[state.curr_ptr] = (Wrapping(state.data[state.curr_ptr]) - Wrapping(n)).0
-        }
-        Op::SetRegisterToZero => state.data[state.curr_ptr] = 0,
+// The bytecode `passes` make of `code`.
+fn vm_program(code: &[char], passes: &PassManager) -> vm::Program {
+    vm::compile(&passes.run(&get_ast(code).0).0)
+}
 
-        Op::Print => state.output.push(state.data[state.curr_ptr]),
-        Op::Read => state.data[state.curr_ptr] = read(&state.output),
+// Runs the program, asking the host for input through `read_val`.
+fn run_js(code: &str, passes: &PassManager) -> RunResult {
+    let mut io = JsIo { pending: Vec::new() };
+    run_result::run(&vm_program(&code.chars().collect::<Vec<_>>(), passes), &mut io, None)
+}
+
+// Like `run_js`, or reports why `code` and `flags` don't make a program.
+fn run_brainfuck(code: &str, flags: &str) -> RunResult {
+    match check(code).and_then(|_| PassManager::from_flags(flags)) {
+        Ok(passes) => run_js(code, &passes),
+        Err(e) => RunResult::error(e),
     }
 }
 
-fn run_brainfuck(code: &str) -> String {
-    let mut state = State {
-        curr_ptr: 0,
-        data: [0; HEAP_SIZE],
-        output: Vec::new(),
+fn run_with_input(code: &str, flags: &str, input: &[u8], max_steps: u32) -> RunResult {
+    let passes = match check(code).and_then(|_| PassManager::from_flags(flags)) {
+        Ok(passes) => passes,
+        Err(e) => return RunResult::error(e),
     };
+    let fuel = if max_steps == 0 { None } else { Some(u64::from(max_steps)) };
+    session::run_with_input(vm_program(&code.chars().collect::<Vec<_>>(), &passes), input, fuel)
+}
 
-    let chars: Vec<char> = code.chars().collect();
-    let (ast, _) = get_ast(&chars);
-    let ast = compact(&ast);
-    eval_vec(&mut state, &ast);
-    String::from_utf8_lossy(state.output.as_slice()).into_owned()
+// The `len` bytes at `ptr`; hosts may pass null for an empty buffer.
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 || ptr.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr, len)
+    }
 }
 
 fn from_c_str(i: *mut c_char) -> String {
     unsafe { CStr::from_ptr(i).to_string_lossy().into_owned() }
 }
 
-fn to_c_str(s: &String) -> *mut c_char {
-    CString::new(s.as_str())
+fn to_c_str(s: &str) -> *mut c_char {
+    CString::new(s)
         .expect("Couldn't convert to string.")
         .into_raw()
 }
 
+/// The program's output, which may contain 0 or bytes that aren't UTF-8,
+/// to be freed with `drop_bytes`; empty for a program that doesn't compile.
 #[no_mangle]
-pub fn js_run_code(code: *mut c_char) -> *mut c_char {
-    let s = from_c_str(code);
-    let output = run_brainfuck(s.as_str());
-    to_c_str(&output)
+pub fn js_run_code(code: *mut c_char) -> *mut JsBytes {
+    JsBytes::new(run_brainfuck(&from_c_str(code), "").output)
 }
 
+/// Like `js_run_code`, for the program `flags` make.
+#[no_mangle]
+pub fn js_run_code_with_flags(code: *mut c_char, flags: *mut c_char) -> *mut JsBytes {
+    with_program(code, &from_c_str(flags), |code, passes| JsBytes::new(run_js(&code, &passes).output))
+}
+
+/// The whole `RunResult` of the optimised program as JSON, see
+/// `RunResult::to_json`; invalid flags are reported as an error there.
+#[no_mangle]
+pub fn js_run_code_json(code: *mut c_char, flags: *mut c_char) -> *mut c_char {
+    let result = run_brainfuck(&from_c_str(code), &from_c_str(flags));
+    to_c_str(&result.to_json())
+}
+
+
+// A session and the output of its last `Output` event.
+pub struct JsSession {
+    session: session::Session,
+    output: Vec<u8>,
+}
+
+/// Starts a program that waits for input with `session_run` returning
+/// instead of prompting. Free it with `session_free`.
+#[no_mangle]
+pub fn session_new(code: *mut c_char, flags: *mut c_char) -> *mut JsSession {
+    with_program(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        let session = session::Session::new(vm::compile(&ast));
+        Box::into_raw(Box::new(JsSession { session, output: Vec::new() }))
+    })
+}
+
+/// Runs at most `fuel` instructions. Returns 0 when the program needs
+/// input, 1 for output (see `session_output`), 2 once it halted and 3 when
+/// the fuel ran out.
+///
+/// # Safety
+///
+/// `session` must come from `session_new` and not have been freed.
+#[no_mangle]
+pub unsafe fn session_run(session: *mut JsSession, fuel: u32) -> u32 {
+    let session = &mut *session;
+    match session.session.run(u64::from(fuel)) {
+        session::Event::NeedsInput => 0,
+        session::Event::Output(bytes) => {
+            session.output = bytes;
+            1
+        }
+        session::Event::Halted => 2,
+        session::Event::OutOfFuel => 3,
+    }
+}
+
+/// The bytes of the last output event, to be freed with `drop_bytes`.
+///
+/// # Safety
+///
+/// `session` must come from `session_new` and not have been freed.
+#[no_mangle]
+pub unsafe fn session_output(session: *mut JsSession) -> *mut JsBytes {
+    JsBytes::new(mem::take(&mut (*session).output))
+}
+
+/// Queues `len` bytes of input, which may contain 0 and need not be text.
+///
+/// # Safety
+///
+/// `session` must come from `session_new` and not have been freed, and
+/// `input` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe fn session_provide_input(session: *mut JsSession, input: *const u8, len: u32) {
+    (*session).session.provide_input(bytes(input, len as usize));
+}
+
+/// Reads past the provided input give 0 from now on.
+///
+/// # Safety
+///
+/// `session` must come from `session_new` and not have been freed.
+#[no_mangle]
+pub unsafe fn session_close_input(session: *mut JsSession) {
+    (*session).session.close_input();
+}
+
+/// The session's state as a snapshot, see `snapshot.rs`.
+///
+/// # Safety
+///
+/// `session` must come from `session_new` and not have been freed.
+#[no_mangle]
+pub unsafe fn session_snapshot(session: *mut JsSession) -> *mut JsBytes {
+    let session = &(*session).session;
+    JsBytes::new(session.snapshot().to_bytes(session.program()))
+}
+
+/// Continues the program from `session_snapshot`'s bytes; null if they
+/// don't belong to this program and flags.
+///
+/// # Safety
+///
+/// `snapshot` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe fn session_from_snapshot(code: *mut c_char, flags: *mut c_char, snapshot: *const u8, len: u32) -> *mut JsSession {
+    let bytes = bytes(snapshot, len as usize);
+    with_program(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        let program = vm::compile(&ast);
+        match snapshot::Snapshot::from_bytes(bytes, &program) {
+            Ok(snapshot) => {
+                let session = session::Session::from_snapshot(program, snapshot);
+                Box::into_raw(Box::new(JsSession { session, output: Vec::new() }))
+            }
+            Err(_) => std::ptr::null_mut(),
+        }
+    })
+}
+
+/// # Safety
+///
+/// `session` must come from `session_new` and must not be used afterwards.
+#[no_mangle]
+pub unsafe fn session_free(session: *mut JsSession) {
+    drop(Box::from_raw(session));
+}
+
+/// The result of `js_run_code_with_input`; `output` has the layout of
+/// `JsBytes`.
+#[repr(C)]
+pub struct JsRunOutput {
+    status: u32,
+    output: JsBytes,
+}
+
+/// Runs the program `flags` make on `input` without calling back into JS.
+/// `max_steps` limits the instructions run, 0 means no limit. `status` is 0 when the
+/// program halted, 1 when it hit the limit and 2 when it did not compile.
+/// Free the result with `drop_run_output`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes.
+#[no_mangle]
+pub unsafe fn js_run_code_with_input(code: *mut c_char, flags: *mut c_char, input: *const u8, input_len: u32,
+                                     max_steps: u32) -> *mut JsRunOutput {
+    let input = bytes(input, input_len as usize);
+    let result = run_with_input(&from_c_str(code), &from_c_str(flags), input, max_steps);
+    Box::into_raw(Box::new(JsRunOutput {
+        status: result.termination.code(),
+        output: JsBytes::from_vec(result.output),
+    }))
+}
+
+/// Like `js_run_code_with_input`, but returns the whole `RunResult` as
+/// JSON.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes.
+#[no_mangle]
+pub unsafe fn js_run_code_with_input_json(code: *mut c_char, flags: *mut c_char, input: *const u8, input_len: u32,
+                                          max_steps: u32) -> *mut c_char {
+    let input = bytes(input, input_len as usize);
+    to_c_str(&run_with_input(&from_c_str(code), &from_c_str(flags), input, max_steps).to_json())
+}
+
+/// # Safety
+///
+/// `ptr` must come from `js_run_code_with_input` and must not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe fn drop_run_output(ptr: *mut JsRunOutput) {
+    Box::from_raw(ptr).output.into_vec();
+}
 
 #[no_mangle]
 pub fn compile_to_wasm(code: *mut c_char) -> *mut JsBytes {
-    let code = from_c_str(code);
-    println!("{}", code);
-    let chars: Vec<char> = code.chars().collect();
-    let (ast, _) = get_ast(&chars);
-    let ast = compact(&ast);
-    let x = wasm::to_wasm(&ast);
-    JsBytes::new(x)
+    with_backend(code, "", |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        JsBytes::new(wasm::to_wasm(&ast, &passes, None))
+    })
+}
+
+#[no_mangle]
+pub fn compile_to_wasm_with_flags(code: *mut c_char, flags: *mut c_char) -> *mut JsBytes {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        JsBytes::new(wasm::to_wasm(&ast, &passes, None))
+    })
+}
+
+/// Like `compile_to_wasm_with_flags`, but embeds the program source in the
+/// module for debugging and provenance.
+#[no_mangle]
+pub fn compile_to_wasm_with_source(code: *mut c_char, flags: *mut c_char) -> *mut JsBytes {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        JsBytes::new(wasm::to_wasm(&ast, &passes, Some(&code)))
+    })
+}
+
+/// One line of statistics per optimisation pass that ran.
+#[no_mangle]
+pub fn optimisation_stats(code: *mut c_char, flags: *mut c_char) -> *mut c_char {
+    with_program(code, &from_c_str(flags), |code, passes| {
+        let (_, stats) = optimise(&code, &passes);
+        let lines: Vec<String> = stats.iter().map(|s| s.to_string()).collect();
+        to_c_str(&lines.join("\n"))
+    })
+}
+
+/// Source map for the module `compile_to_wasm_with_source_map` returns.
+#[no_mangle]
+pub fn wasm_source_map(code: *mut c_char, flags: *mut c_char, map_url: *mut c_char) -> *mut c_char {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        let (_, map) = wasm::to_wasm_with_source_map(&ast, &passes, &code, &from_c_str(map_url));
+        to_c_str(&map)
+    })
+}
+
+/// The compiled module, with a `sourceMappingURL` section pointing at
+/// `map_url`.
+#[no_mangle]
+pub fn compile_to_wasm_with_source_map(code: *mut c_char, flags: *mut c_char, map_url: *mut c_char) -> *mut JsBytes {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        let (module, _) = wasm::to_wasm_with_source_map(&ast, &passes, &code, &from_c_str(map_url));
+        JsBytes::new(module)
+    })
+}
+
+/// A module that records which parts of the program ran, see
+/// `wasm::to_wasm_with_coverage`. It is built from the program as written,
+/// so null for flags that would remove code.
+#[no_mangle]
+pub fn compile_to_wasm_with_coverage(code: *mut c_char, flags: *mut c_char) -> *mut JsBytes {
+    with_coverage(code, flags, |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        JsBytes::new(wasm::to_wasm_with_coverage(&ast, &passes).0)
+    })
+}
+
+/// lcov for a run of the module from `compile_to_wasm_with_coverage`;
+/// `hits` has a '0' or '1' for each of its coverage bytes.
+#[no_mangle]
+pub fn wasm_coverage_lcov(code: *mut c_char, flags: *mut c_char, hits: *mut c_char) -> *mut c_char {
+    with_coverage(code, flags, |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        let (_, points) = wasm::to_wasm_with_coverage(&ast, &passes);
+        let hits = from_c_str(hits);
+        let counts: Vec<_> = points.into_iter()
+            .zip(hits.chars().map(|c| u64::from(c == '1')))
+            .collect();
+        let source: Vec<char> = code.chars().collect();
+        to_c_str(&coverage::lcov(&source, "program.bf", &counts))
+    })
+}
+
+#[no_mangle]
+pub fn compile_to_c(code: *mut c_char, flags: *mut c_char) -> *mut c_char {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        to_c_str(&c::to_c(&ast, &c::Options::default()))
+    })
+}
+
+#[no_mangle]
+pub fn compile_to_rust(code: *mut c_char, flags: *mut c_char) -> *mut c_char {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        to_c_str(&rust::to_rust(&ast))
+    })
+}
+
+#[no_mangle]
+pub fn compile_to_js(code: *mut c_char, flags: *mut c_char) -> *mut c_char {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        to_c_str(&js::to_js(&ast))
+    })
+}
+
+#[no_mangle]
+pub fn compile_to_llvm(code: *mut c_char, flags: *mut c_char) -> *mut c_char {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        to_c_str(&llvm::to_llvm(&ast))
+    })
+}
+
+/// A class file declaring `Main`; null when the program is too large for
+/// a JVM method.
+#[no_mangle]
+pub fn compile_to_class(code: *mut c_char, flags: *mut c_char) -> *mut JsBytes {
+    with_backend(code, &from_c_str(flags), |code, passes| {
+        let (ast, _) = optimise(&code, &passes);
+        match jvm::to_class(&ast, "Main") {
+            Ok(class) => JsBytes::new(class),
+            Err(_) => std::ptr::null_mut(),
+        }
+    })
 }
 
+// The web page only calls the exports, never `main`.
 fn main() {
-}
\ No newline at end of file
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = cli::run(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+// Every export whose result the page frees must give back all it allocated:
+// a host that calls them in a loop otherwise grows its memory on each call.
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::ffi::CString;
+    use std::os::raw::c_char;
+    use super::*;
+
+    // Counts the bytes this thread has live, so that tests running
+    // alongside on other threads don't disturb the count.
+    struct Counting;
+
+    thread_local!(static LIVE: Cell<isize> = const { Cell::new(0) });
+
+    fn track(delta: isize) {
+        let _ = LIVE.try_with(|live| live.set(live.get() + delta));
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            track(layout.size() as isize);
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            track(-(layout.size() as isize));
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            track(new_size as isize - layout.size() as isize);
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    // The page links its own `read_val`; tests read zeros.
+    #[no_mangle]
+    pub extern "C" fn read_val(_: *const u8, _: usize) -> u8 {
+        0
+    }
+
+    const PROGRAM: &str = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.>---.+++++++..+++.,[-]";
+
+    // Runs `f` a few times and checks the live bytes end where they began.
+    fn assert_no_growth<F: FnMut()>(mut f: F) {
+        f();
+        let before = LIVE.with(Cell::get);
+        for _ in 0..50 {
+            f();
+        }
+        assert_eq!(LIVE.with(Cell::get), before);
+    }
+
+    fn c_str(s: &str) -> *mut c_char {
+        CString::new(s).unwrap().into_raw()
+    }
+
+    unsafe fn free(ptr: *mut c_char) {
+        drop(CString::from_raw(ptr));
+    }
+
+    #[test]
+    fn compiling_does_not_leak() {
+        assert_no_growth(|| unsafe {
+            let (code, flags) = (c_str(PROGRAM), c_str(""));
+            drop_bytes(compile_to_wasm(code));
+            drop_bytes(compile_to_wasm_with_flags(code, flags));
+            drop_bytes(compile_to_class(code, flags));
+            free_string(compile_to_js(code, flags));
+            free_string(compile_to_c(code, flags));
+            free_string(optimisation_stats(code, flags));
+            free(code);
+            free(flags);
+        });
+    }
+
+    #[test]
+    fn running_does_not_leak() {
+        assert_no_growth(|| unsafe {
+            let (code, flags) = (c_str(PROGRAM), c_str(""));
+            drop_bytes(js_run_code(code));
+            drop_bytes(js_run_code_with_flags(code, flags));
+            free_string(js_run_code_json(code, flags));
+            let input = alloc_bytes(3);
+            drop_run_output(js_run_code_with_input(code, flags, input, 3, 0));
+            free_string(js_run_code_with_input_json(code, flags, input, 3, 1000));
+            free_bytes(input, 3);
+            free(code);
+            free(flags);
+        });
+    }
+
+    #[test]
+    fn sessions_do_not_leak() {
+        assert_no_growth(|| unsafe {
+            let (code, flags) = (c_str(PROGRAM), c_str(""));
+            let session = session_new(code, flags);
+            session_run(session, 100);
+            drop_bytes(session_output(session));
+            session_provide_input(session, b"x".as_ptr(), 1);
+            session_close_input(session);
+            let snapshot = session_snapshot(session);
+            let restored = session_from_snapshot(code, flags, (*snapshot).ptr as *const u8, (*snapshot).len as u32);
+            session_run(restored, 0);
+            session_free(restored);
+            drop_bytes(snapshot);
+            session_free(session);
+            free(code);
+            free(flags);
+        });
+    }
+
+    unsafe fn output(session: *mut JsSession) -> Vec<u8> {
+        let output = session_output(session);
+        let vec = bytes((*output).ptr as *const u8, (*output).len).to_vec();
+        drop_bytes(output);
+        vec
+    }
+
+    #[test]
+    fn sessions_hand_back_control_in_order() {
+        unsafe {
+            let (code, flags) = (c_str(",.,."), c_str(""));
+            let session = session_new(code, flags);
+            assert_eq!(session_run(session, 0), 3);
+            assert_eq!(session_run(session, 100), 0);
+            session_provide_input(session, b"\0".as_ptr(), 1);
+            assert_eq!(session_run(session, 100), 1);
+            assert_eq!(output(session), [0]);
+            let snapshot = session_snapshot(session);
+
+            assert_eq!(session_run(session, 100), 0);
+            session_provide_input(session, b"\xff".as_ptr(), 1);
+            assert_eq!(session_run(session, 100), 1);
+            assert_eq!(output(session), [255]);
+            assert_eq!(session_run(session, 100), 2);
+            session_free(session);
+
+            // the input queued after the snapshot is not part of it
+            let resumed = session_from_snapshot(code, flags, (*snapshot).ptr as *const u8, (*snapshot).len as u32);
+            assert!(!resumed.is_null());
+            assert_eq!(session_run(resumed, 100), 0);
+            session_provide_input(resumed, std::ptr::null(), 0);
+            assert_eq!(session_run(resumed, 100), 0);
+            session_close_input(resumed);
+            assert_eq!(session_run(resumed, 100), 1);
+            assert_eq!(output(resumed), [0]);
+            assert_eq!(session_run(resumed, 100), 2);
+            session_free(resumed);
+
+            assert!(session_from_snapshot(code, flags, std::ptr::null(), 0).is_null());
+            let other = c_str(",.,.,.");
+            assert!(session_from_snapshot(other, flags, (*snapshot).ptr as *const u8, (*snapshot).len as u32).is_null());
+            drop_bytes(snapshot);
+            for ptr in &[code, flags, other] {
+                free(*ptr);
+            }
+        }
+    }
+
+    #[test]
+    fn web_exports_do_not_leak() {
+        use web::*;
+        assert_no_growth(|| unsafe {
+            let (code, flags) = (PROGRAM.as_ptr(), "".as_ptr());
+            for target in &["wasm", "js", "class", "cobol"] {
+                drop_result(web_compile(code, PROGRAM.len(), flags, 0, target.as_ptr(), target.len()));
+            }
+            drop_result(web_run(code, PROGRAM.len(), flags, 0, 0));
+            drop_result(web_run_with_input(code, PROGRAM.len(), flags, 0, "x".as_ptr(), 1, 1000));
+            drop_result(web_format(code, PROGRAM.len()));
+            drop_result(web_analyse(code, PROGRAM.len(), flags, 0));
+        });
+    }
+
+    unsafe fn run_on(code: &str, input: &[u8], max_steps: u32) -> (u32, Vec<u8>) {
+        let (code, flags) = (c_str(code), c_str("-O0"));
+        let result = js_run_code_with_input(code, flags, input.as_ptr(), input.len() as u32, max_steps);
+        let output = bytes((*result).output.ptr as *const u8, (*result).output.len).to_vec();
+        let status = (*result).status;
+        drop_run_output(result);
+        free(code);
+        free(flags);
+        (status, output)
+    }
+
+    #[test]
+    fn runs_on_the_given_input() {
+        unsafe {
+            assert_eq!(run_on(",[.,]", b"hi", 0), (0, b"hi".to_vec()));
+            assert_eq!(run_on(",.,.", b"\0\xff", 0), (0, vec![0, 255]));
+            // reads past the end give 0
+            assert_eq!(run_on(",+.,+.", b"a", 0), (0, b"b\x01".to_vec()));
+            assert_eq!(run_on(",+.", b"", 0), (0, vec![1]));
+            assert_eq!(run_on(",+.", b"", 0), run_on(",+.", b"\0", 0));
+            assert_eq!(run_on("+[", b"", 0), (2, vec![]));
+        }
+    }
+
+    #[test]
+    fn stops_after_max_steps() {
+        unsafe {
+            assert_eq!(run_on("+.+[]", b"", 0xffff), (1, vec![1]));
+            assert_eq!(run_on("+.+.", b"", 3), (1, vec![1]));
+            assert_eq!(run_on("+.+.", b"", 4), (0, vec![1, 2]));
+            let (code, flags) = (c_str("+[.]"), c_str("-O0"));
+            let json = js_run_code_with_input_json(code, flags, std::ptr::null(), 0, 10);
+            assert_eq!(CStr::from_ptr(json).to_str().unwrap().split(",\"elapsed_ms\"").next(),
+                       Some("{\"output\":[1,1,1,1],\"text\":\"\\u0001\\u0001\\u0001\\u0001\",\"tape\":[1],\"ptr\":0,\"steps\":10"));
+            assert!(CStr::from_ptr(json).to_str().unwrap().ends_with("\"termination\":\"out-of-fuel\"}"));
+            free_string(json);
+            free(code);
+            free(flags);
+        }
+    }
+
+    // The JSON of a run, without the time it took.
+    unsafe fn run_json(code: &str, flags: &str) -> String {
+        let (code, flags) = (c_str(code), c_str(flags));
+        let json = js_run_code_json(code, flags);
+        let res = CStr::from_ptr(json).to_str().unwrap().to_owned();
+        free_string(json);
+        free(code);
+        free(flags);
+        let (start, end) = (res.find(",\"elapsed_ms\"").unwrap(), res.find(",\"termination\"").unwrap());
+        res[..start].to_owned() + &res[end..]
+    }
+
+    #[test]
+    fn runs_the_program_the_flags_make() {
+        unsafe {
+            // partial evaluation folds the whole program, yet leaves the
+            // tape and the pointer where the program did
+            let halted = |steps| format!("{{\"output\":[3],\"text\":\"\\u0003\",\"tape\":[2,3],\"ptr\":1,\"steps\":{},\"termination\":\"halted\"}}", steps);
+            assert_eq!(run_json("++>+++.", ""), halted(2));
+            assert_eq!(run_json("++>+++.", "-O0 -fpartial-eval"), halted(2));
+            assert_eq!(run_json("++>+++.", "-O2"), halted(4));
+            assert_eq!(run_json("++>+++.", "-O0"), halted(7));
+            assert!(run_json("++>+++.", "-O9").ends_with("\"termination\":\"error\",\"error\":\"unknown flag '-O9'\"}"));
+        }
+    }
+
+    #[test]
+    fn runs_return_output_with_zeros() {
+        unsafe {
+            let (code, flags) = (c_str(".+.-."), c_str("-O1"));
+            for output in &[js_run_code(code), js_run_code_with_flags(code, flags)] {
+                assert_eq!(bytes((**output).ptr as *const u8, (**output).len), [0, 1, 0]);
+                drop_bytes(*output);
+            }
+            free(code);
+            free(flags);
+        }
+    }
+
+    #[test]
+    fn only_backends_limit_nesting() {
+        let nested = |depth| c_str(&format!("+{}-{}+.", "[".repeat(depth), "]".repeat(depth)));
+        unsafe {
+            let (ok, deep, deeper, flags) = (nested(1000), nested(1001), nested(100_000), c_str(""));
+            let wasm = compile_to_wasm(ok);
+            assert!(!wasm.is_null());
+            drop_bytes(wasm);
+            free_string(compile_to_c(ok, flags));
+            assert!(compile_to_wasm(deep).is_null());
+            assert!(compile_to_c(deep, flags).is_null());
+            for code in &[deep, deeper] {
+                let json = js_run_code_json(*code, flags);
+                assert!(CStr::from_ptr(json).to_str().unwrap().starts_with("{\"output\":[1],"));
+                free_string(json);
+                session_free(session_new(*code, flags));
+            }
+            for ptr in &[ok, deep, deeper, flags] {
+                free(*ptr);
+            }
+        }
+    }
+
+    #[test]
+    fn coverage_reports_on_the_program_as_written() {
+        let program = "[never . run]\n+++[>+<-]\n>.\n[-]<[never]";
+        let (_, points) = wasm::to_wasm_with_coverage(&get_ast(&program.chars().collect::<Vec<_>>()).0,
+                                                      &PassManager::new(passes::OptLevel::O0));
+        unsafe {
+            let (code, flags, hits) = (c_str(program), c_str(""), c_str(&"1".repeat(points.len())));
+            let module = compile_to_wasm_with_coverage(code, flags);
+            assert!(!module.is_null());
+            drop_bytes(module);
+            let lcov = wasm_coverage_lcov(code, flags, hits);
+            assert!(CStr::from_ptr(lcov).to_str().unwrap().contains("\nFNF:4\nFNH:4\n"));
+            free_string(lcov);
+            let optimised = c_str("-O3");
+            assert!(compile_to_wasm_with_coverage(code, optimised).is_null());
+            assert!(wasm_coverage_lcov(code, optimised, hits).is_null());
+            for ptr in &[code, flags, hits, optimised] {
+                free(*ptr);
+            }
+        }
+    }
+
+    #[test]
+    fn rejected_programs_do_not_leak() {
+        assert_no_growth(|| unsafe {
+            let (code, flags) = (c_str("+["), c_str("nonsense"));
+            assert!(compile_to_js(code, flags).is_null());
+            assert!(session_new(code, flags).is_null());
+            free_string(js_run_code_json(code, flags));
+            free(code);
+            free(flags);
+        });
+    }
+}
@@ -0,0 +1,132 @@
+use brainfuck::*;
+
+// Upper bound of ops executed at compile time, so that long running or
+// non-terminating programs still compile quickly.
+pub const BUDGET: usize = 1_000_000;
+
+struct Machine {
+    tape: Vec<u8>,
+    ptr: usize,
+    output: Vec<u8>,
+    steps: usize,
+}
+
+// Why evaluation had to stop before an op.
+enum Stop {
+    Input,
+    Budget,
+    OutOfTape,
+}
+
+fn tick(m: &mut Machine) -> Result<(), Stop> {
+    m.steps += 1;
+    if m.steps > BUDGET {
+        return Err(Stop::Budget);
+    }
+    Ok(())
+}
+
+// Loops keep their place on `loops` rather than on the call stack, so any
+// nesting depth evaluates.
+fn exec(m: &mut Machine, op: &Op) -> Result<(), Stop> {
+    // the bodies of the loops being run, innermost last, and their next op
+    let mut loops: Vec<(&[Node], usize)> = Vec::new();
+    let mut next = Some(op);
+    loop {
+        if let Some(op) = next.take() {
+            tick(m)?;
+            match *op {
+                Op::IncPointer(n) => {
+                    if m.ptr + n >= HEAP_SIZE {
+                        return Err(Stop::OutOfTape);
+                    }
+                    m.ptr += n;
+                }
+                Op::DecPointer(n) => {
+                    if n > m.ptr {
+                        return Err(Stop::OutOfTape);
+                    }
+                    m.ptr -= n;
+                }
+                Op::IncVal(n) => m.tape[m.ptr] = m.tape[m.ptr].wrapping_add(n),
+                Op::DecVal(n) => m.tape[m.ptr] = m.tape[m.ptr].wrapping_sub(n),
+                Op::SetRegisterToZero => m.tape[m.ptr] = 0,
+                Op::Print => m.output.push(m.tape[m.ptr]),
+                Op::PrintBytes(ref bytes) => m.output.extend_from_slice(bytes),
+                Op::Read => return Err(Stop::Input),
+                Op::InitTape { ref data, ptr } => {
+                    m.tape[..data.len()].copy_from_slice(data);
+                    m.ptr = ptr;
+                }
+                Op::While { ref ops } => {
+                    if m.tape[m.ptr] != 0 {
+                        // each iteration counts, or `[]` would never use up
+                        // the budget
+                        tick(m)?;
+                        loops.push((ops, 0));
+                    }
+                }
+            }
+        }
+        let (body, i) = match loops.last_mut() {
+            Some(&mut (body, ref mut i)) => (body, i),
+            None => return Ok(()),
+        };
+        if *i < body.len() {
+            next = Some(&body[*i].op);
+            *i += 1;
+        } else if m.tape[m.ptr] != 0 {
+            tick(m)?;
+            *i = 0;
+        } else {
+            loops.pop();
+        }
+    }
+}
+
+/// Runs the deterministic prefix of the program (everything before the
+/// first `,` or until the budget runs out) at compile time and replaces it
+/// with the resulting tape and output.
+pub fn partial_eval(ast: &[Node]) -> Vec<Node> {
+    let mut m = Machine {
+        tape: vec![0; HEAP_SIZE],
+        ptr: 0,
+        output: Vec::new(),
+        steps: 0,
+    };
+
+    let mut evaluated = 0;
+    for node in ast {
+        let backup = (m.tape.clone(), m.ptr, m.output.len());
+        match exec(&mut m, &node.op) {
+            Ok(()) => evaluated += 1,
+            Err(_) => {
+                m.tape = backup.0;
+                m.ptr = backup.1;
+                m.output.truncate(backup.2);
+                break;
+            }
+        }
+    }
+
+    if evaluated == 0 {
+        return ast.to_vec();
+    }
+
+    let rest = &ast[evaluated..];
+    let span = merge_spans(&ast[..evaluated]).unwrap_or((0, 0));
+    let mut result = Vec::new();
+    let used = m.tape.iter().rposition(|v| *v != 0).map_or(0, |i| i + 1);
+    if used > 0 || m.ptr != 0 {
+        m.tape.truncate(used);
+        result.push(Node::new(Op::InitTape {
+            data: m.tape,
+            ptr: m.ptr,
+        }, span));
+    }
+    if !m.output.is_empty() {
+        result.push(Node::new(Op::PrintBytes(m.output), span));
+    }
+    result.extend_from_slice(rest);
+    result
+}
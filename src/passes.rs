@@ -0,0 +1,291 @@
+use brainfuck::*;
+use partial_eval::partial_eval;
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Pass {
+    Compact,
+    DeadCode,
+    PartialEval,
+    /// Works on the generated wasm instead of the `Op` tree, so it is only
+    /// consulted by the wasm backend.
+    Peephole,
+}
+
+const ALL_PASSES: [Pass; 4] = [Pass::Compact, Pass::DeadCode, Pass::PartialEval, Pass::Peephole];
+
+impl Pass {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Pass::Compact => "compact",
+            Pass::DeadCode => "dead-code",
+            Pass::PartialEval => "partial-eval",
+            Pass::Peephole => "peephole",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Pass> {
+        ALL_PASSES.iter().cloned().find(|p| p.name() == name)
+    }
+
+    fn apply(&self, ast: &[Node]) -> Vec<Node> {
+        match *self {
+            Pass::Compact => compact(ast),
+            Pass::DeadCode => remove_dead_code(ast),
+            Pass::PartialEval => partial_eval(ast),
+            Pass::Peephole => ast.to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OptLevel {
+    O0,
+    O1,
+    O2,
+    O3,
+}
+
+impl OptLevel {
+    fn passes(&self) -> Vec<Pass> {
+        match *self {
+            OptLevel::O0 => vec![],
+            OptLevel::O1 => vec![Pass::Compact, Pass::Peephole],
+            OptLevel::O2 => vec![Pass::Compact, Pass::DeadCode, Pass::Peephole],
+            OptLevel::O3 => vec![Pass::Compact, Pass::DeadCode, Pass::PartialEval, Pass::Peephole],
+        }
+    }
+}
+
+pub struct PassStats {
+    pub pass: Pass,
+    pub ops_before: usize,
+    pub ops_after: usize,
+    pub loops_before: usize,
+    pub loops_after: usize,
+}
+
+impl PassStats {
+    pub fn ops_removed(&self) -> isize {
+        self.ops_before as isize - self.ops_after as isize
+    }
+
+    pub fn loops_transformed(&self) -> usize {
+        self.loops_before.saturating_sub(self.loops_after)
+    }
+}
+
+impl fmt::Display for PassStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} -> {} ops ({} removed), {} -> {} loops ({} transformed)",
+               self.pass.name(),
+               self.ops_before, self.ops_after, self.ops_removed(),
+               self.loops_before, self.loops_after, self.loops_transformed())
+    }
+}
+
+// Returns the number of ops and loops in the tree, nested ones included.
+fn count(ast: &[Node]) -> (usize, usize) {
+    let mut ops = 0;
+    let mut loops = 0;
+    let mut lists = vec![ast];
+    while let Some(list) = lists.pop() {
+        ops += list.len();
+        for node in list {
+            if let Op::While { ops: ref body } = node.op {
+                loops += 1;
+                lists.push(body);
+            }
+        }
+    }
+    (ops, loops)
+}
+
+/// The set of enabled optimisation passes, always run in a fixed order.
+pub struct PassManager {
+    passes: Vec<Pass>,
+}
+
+impl PassManager {
+    pub fn new(level: OptLevel) -> PassManager {
+        PassManager { passes: level.passes() }
+    }
+
+    /// Parses compiler-like flags, e.g. `-O2 -fno-dead-code -fpartial-eval`.
+    pub fn from_flags(flags: &str) -> Result<PassManager, String> {
+        PassManager::default().with_flags(flags)
+    }
+
+    fn with_flags(self, flags: &str) -> Result<PassManager, String> {
+        let mut manager = self;
+        for flag in flags.split_whitespace() {
+            let level = match flag {
+                "-O0" => Some(OptLevel::O0),
+                "-O1" => Some(OptLevel::O1),
+                "-O2" => Some(OptLevel::O2),
+                "-O3" => Some(OptLevel::O3),
+                _ => None,
+            };
+            if let Some(level) = level {
+                manager = PassManager::new(level);
+            } else if let Some(name) = flag.strip_prefix("-fno-") {
+                match Pass::from_name(name) {
+                    Some(pass) => manager.disable(pass),
+                    None => return Err(format!("unknown pass in '{}'", flag)),
+                }
+            } else if let Some(name) = flag.strip_prefix("-f") {
+                match Pass::from_name(name) {
+                    Some(pass) => manager.enable(pass),
+                    None => return Err(format!("unknown pass in '{}'", flag)),
+                }
+            } else {
+                return Err(format!("unknown flag '{}'", flag));
+            }
+        }
+        Ok(manager)
+    }
+
+    /// Passes for a mode that reports on the source, such as the debugger:
+    /// `flags` on top of `default`, and an error for flags that would remove
+    /// code. `Compact` only merges neighbouring ops, so it may run.
+    pub fn for_source(flags: &str, default: &[Pass]) -> Result<PassManager, String> {
+        let manager = PassManager { passes: default.to_vec() }.with_flags(flags)?;
+        match [Pass::DeadCode, Pass::PartialEval].iter().find(|p| manager.is_enabled(**p)) {
+            Some(pass) => Err(format!("{} removes code; use -O0, -O1 or -fcompact", pass.name())),
+            None => Ok(manager),
+        }
+    }
+
+    pub fn enable(&mut self, pass: Pass) {
+        if !self.is_enabled(pass) {
+            self.passes.push(pass);
+        }
+    }
+
+    pub fn disable(&mut self, pass: Pass) {
+        self.passes.retain(|p| *p != pass);
+    }
+
+    pub fn is_enabled(&self, pass: Pass) -> bool {
+        self.passes.contains(&pass)
+    }
+
+    pub fn run(&self, ast: &[Node]) -> (Vec<Node>, Vec<PassStats>) {
+        let mut ast = ast.to_vec();
+        let mut stats = Vec::new();
+        for pass in ALL_PASSES.iter().filter(|p| self.is_enabled(**p) && **p != Pass::Peephole) {
+            let (ops_before, loops_before) = count(&ast);
+            ast = pass.apply(&ast);
+            let (ops_after, loops_after) = count(&ast);
+            stats.push(PassStats {
+                pass: *pass,
+                ops_before,
+                ops_after,
+                loops_before,
+                loops_after,
+            });
+        }
+        (ast, stats)
+    }
+}
+
+impl Default for PassManager {
+    fn default() -> PassManager {
+        PassManager::new(OptLevel::O3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use session;
+    use vm;
+
+    fn output(code: &str, flags: &str) -> Vec<u8> {
+        let passes = PassManager::from_flags(flags).unwrap();
+        let chars: Vec<char> = code.chars().collect();
+        let (ast, _) = passes.run(&get_ast(&chars).0);
+        session::run_with_input(vm::compile(&ast), b"", None).output
+    }
+
+    #[test]
+    fn presets_enable_their_passes() {
+        let enabled = |flags| {
+            let manager = PassManager::from_flags(flags).unwrap();
+            ALL_PASSES.iter().cloned().filter(|&p| manager.is_enabled(p)).collect::<Vec<_>>()
+        };
+        assert_eq!(enabled("-O0"), vec![]);
+        assert_eq!(enabled("-O1"), vec![Pass::Compact, Pass::Peephole]);
+        assert_eq!(enabled("-O2"), vec![Pass::Compact, Pass::DeadCode, Pass::Peephole]);
+        assert_eq!(enabled("-O3"), ALL_PASSES.to_vec());
+        assert_eq!(enabled(""), ALL_PASSES.to_vec());
+        assert_eq!(enabled("-O3 -O1"), vec![Pass::Compact, Pass::Peephole]);
+    }
+
+    #[test]
+    fn flags_toggle_passes_after_the_preset() {
+        let manager = PassManager::from_flags("-O2 -fno-dead-code -fpartial-eval").unwrap();
+        assert!(manager.is_enabled(Pass::Compact));
+        assert!(!manager.is_enabled(Pass::DeadCode));
+        assert!(manager.is_enabled(Pass::PartialEval));
+        // a later preset starts over
+        assert!(PassManager::from_flags("-fno-compact -O1").unwrap().is_enabled(Pass::Compact));
+    }
+
+    #[test]
+    fn source_modes_refuse_passes_that_remove_code() {
+        assert!(!PassManager::for_source("", &[]).unwrap().is_enabled(Pass::Compact));
+        assert!(PassManager::for_source("-fcompact", &[]).unwrap().is_enabled(Pass::Compact));
+        assert!(PassManager::for_source("-O3 -fno-dead-code -fno-partial-eval", &[]).is_ok());
+        assert_eq!(PassManager::for_source("-O2", &[]).err(),
+                   Some("dead-code removes code; use -O0, -O1 or -fcompact".to_owned()));
+        assert_eq!(PassManager::for_source("-fpartial-eval", &[Pass::Compact]).err(),
+                   Some("partial-eval removes code; use -O0, -O1 or -fcompact".to_owned()));
+    }
+
+    #[test]
+    fn rejects_unknown_flags() {
+        assert_eq!(PassManager::from_flags("-O4").err(), Some("unknown flag '-O4'".to_owned()));
+        assert_eq!(PassManager::from_flags("-fno-inline").err(), Some("unknown pass in '-fno-inline'".to_owned()));
+        assert_eq!(PassManager::from_flags("-fbogus").err(), Some("unknown pass in '-fbogus'".to_owned()));
+        assert_eq!(PassManager::from_flags("--run").err(), Some("unknown flag '--run'".to_owned()));
+    }
+
+    #[test]
+    fn every_pass_handles_any_nesting_depth() {
+        for &depth in &[1000, 1001, 100_000] {
+            let code = format!("+{}-{}++.", "[".repeat(depth), "]".repeat(depth));
+            for flags in &["-O0", "-O1", "-O2", "-O3", "-O0 -fdead-code", "-O0 -fpartial-eval"] {
+                assert_eq!(output(&code, flags), [2], "depth {} with {}", depth, flags);
+            }
+        }
+    }
+
+    // Each program moves the pointer past an end of the tape and comes back
+    // to a cell a pass could mistake for an untouched one.
+    fn wrapping_programs() -> Vec<String> {
+        let right = ">".repeat(HEAP_SIZE);
+        let left = "<".repeat(HEAP_SIZE);
+        vec![
+            // past the right end back onto cell 0
+            format!("+{}[.-]", right),
+            // past the left end onto the last cell, then there from the left
+            format!("<+{}[.-]", right),
+            // a tape's length apart from a position after a loop
+            format!("+[-]{}+++++{}[.-]", right, left),
+            format!("+[-]{}+++<{}>[.-]", left, right),
+        ]
+    }
+
+    #[test]
+    fn passes_keep_the_output_of_programs_that_wrap_around() {
+        for code in wrapping_programs() {
+            let expected = output(&code, "-O0");
+            assert!(!expected.is_empty());
+            for flags in &["-O1", "-O2", "-O3", "-O0 -fcompact", "-O0 -fdead-code", "-O0 -fpartial-eval",
+                           "-O0 -fdead-code -fpartial-eval"] {
+                assert_eq!(output(&code, flags), expected, "{} with {}", &code[..10], flags);
+            }
+        }
+    }
+}
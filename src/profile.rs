@@ -0,0 +1,170 @@
+// Counts how often each bytecode instruction runs and attributes the
+// counts to source positions and loops.
+use brainfuck::*;
+use interpreter::Io;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use vm::{Instr, Machine, Program};
+
+pub struct Profile<'a> {
+    program: &'a Program,
+    /// Executions of every instruction in `program.code`.
+    pub counts: Vec<u64>,
+}
+
+pub struct LoopStats {
+    pub span: Span,
+    /// Times the loop was reached, including those that skip it.
+    pub entries: u64,
+    pub iterations: u64,
+    /// Instructions run inside the loop, nested loops included.
+    pub instructions: u64,
+}
+
+impl LoopStats {
+    pub fn average_iterations(&self) -> f64 {
+        if self.entries == 0 {
+            0.0
+        } else {
+            self.iterations as f64 / self.entries as f64
+        }
+    }
+}
+
+/// Runs the program like `vm::run` and counts what it executes.
+pub fn run<'a>(program: &'a Program, io: &mut dyn Io) -> Profile<'a> {
+    let mut counts = vec![0; program.code.len()];
+    let mut machine = Machine::new();
+    while !machine.halted(program) {
+        counts[machine.pc] += 1;
+        machine.step(program, io);
+    }
+    Profile { program, counts }
+}
+
+impl<'a> Profile<'a> {
+    pub fn program(&self) -> &'a Program {
+        self.program
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Executions per source char. The test of a loop counts for its `[`
+    /// and the jump back for its `]`; other instructions count for every
+    /// char they were compiled from.
+    pub fn per_position(&self, source_len: usize) -> Vec<u64> {
+        let mut counts = vec![0; source_len];
+        for (pc, &(start, end)) in self.program.spans.iter().enumerate() {
+            let chars = match self.program.code[pc] {
+                Instr::JumpIfZero(_) => start..start + 1,
+                Instr::JumpIfNotZero(_) => end - 1..end,
+                _ => start..end,
+            };
+            for count in &mut counts[chars.start.min(source_len)..chars.end.min(source_len)] {
+                *count += self.counts[pc];
+            }
+        }
+        counts
+    }
+
+    /// All loops, the ones running the most instructions first.
+    pub fn loops(&self) -> Vec<LoopStats> {
+        let mut loops: Vec<LoopStats> = self.program.code.iter().enumerate()
+            .filter_map(|(pc, instr)| match *instr {
+                Instr::JumpIfZero(after) => Some(LoopStats {
+                    span: self.program.spans[pc],
+                    entries: self.counts[pc],
+                    iterations: self.counts[after - 1],
+                    instructions: self.counts[pc + 1..after].iter().sum(),
+                }),
+                _ => None,
+            })
+            .collect();
+        loops.sort_by(|a, b| b.instructions.cmp(&a.instructions).then(a.span.cmp(&b.span)));
+        loops
+    }
+
+    /// The source, each line prefixed with the count of its hottest char.
+    pub fn annotated(&self, source: &[char]) -> String {
+        let counts = self.per_position(source.len());
+        let mut res = String::new();
+        let mut start = 0;
+        for line in source.split(|&c| c == '\n') {
+            let hottest = counts[start..start + line.len()].iter().max().cloned().unwrap_or(0);
+            let text: String = line.iter().collect();
+            writeln!(res, "{:>12} | {}", hottest, text).unwrap();
+            start += line.len() + 1;
+        }
+        res
+    }
+
+    /// Instructions per stack of nested loops, in the folded format
+    /// flamegraph tools read: `main;loop@1:3;loop@2:5 1234`.
+    pub fn folded(&self, source: &[char]) -> String {
+        let mut stacks: BTreeMap<String, u64> = BTreeMap::new();
+        let mut stack = vec!["main".to_owned()];
+        for (pc, instr) in self.program.code.iter().enumerate() {
+            *stacks.entry(stack.join(";")).or_insert(0) += self.counts[pc];
+            match *instr {
+                Instr::JumpIfZero(_) => {
+                    let (line, col) = line_col(source, self.program.spans[pc].0);
+                    stack.push(format!("loop@{}:{}", line, col));
+                }
+                Instr::JumpIfNotZero(_) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+        let mut res = String::new();
+        for (stack, count) in stacks.iter().filter(|&(_, &count)| count > 0) {
+            writeln!(res, "{} {}", stack, count).unwrap();
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vm;
+
+    struct NullIo;
+
+    impl Io for NullIo {
+        fn print(&mut self, _: u8) {}
+
+        fn read(&mut self) -> u8 {
+            0
+        }
+    }
+
+    //                   0123456789012345
+    const PROGRAM: &str = "+++[>++[>+<-]<-]";
+
+    fn program() -> Program {
+        let (ast, _) = get_ast(&PROGRAM.chars().collect::<Vec<_>>());
+        vm::compile(&compact(&ast))
+    }
+
+    #[test]
+    fn counts_loop_entries_and_iterations() {
+        let program = program();
+        let profile = run(&program, &mut NullIo);
+        assert_eq!(profile.steps(), 50);
+        let loops: Vec<_> = profile.loops().iter()
+            .map(|l| (l.span, l.entries, l.iterations, l.instructions)).collect();
+        assert_eq!(loops, [((3, 16), 1, 3, 48), ((7, 13), 3, 6, 30)]);
+    }
+
+    #[test]
+    fn reports_per_char_and_per_stack() {
+        let program = program();
+        let profile = run(&program, &mut NullIo);
+        let source: Vec<char> = PROGRAM.chars().collect();
+        assert_eq!(profile.per_position(source.len()), [1, 1, 1, 1, 3, 3, 3, 3, 6, 6, 6, 6, 6, 3, 3, 3]);
+        assert_eq!(profile.folded(&source), "main 2\nmain;loop@1:4 18\nmain;loop@1:4;loop@1:8 30\n");
+    }
+}
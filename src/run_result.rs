@@ -0,0 +1,311 @@
+// Everything a finished run leaves behind, for hosts that want more than
+// the output text.
+use interpreter::Io;
+use json;
+use std::time::Duration;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::Instant;
+use vm::{Machine, Program};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Termination {
+    Halted,
+    /// Stopped after the instruction limit.
+    OutOfFuel,
+    /// The program could not be compiled.
+    Error(String),
+}
+
+impl Termination {
+    /// 0 for a program that halted.
+    pub fn code(&self) -> u32 {
+        match *self {
+            Termination::Halted => 0,
+            Termination::OutOfFuel => 1,
+            Termination::Error(_) => 2,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Termination::Halted => "halted",
+            Termination::OutOfFuel => "out-of-fuel",
+            Termination::Error(_) => "error",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RunResult {
+    pub output: Vec<u8>,
+    pub tape: Vec<u8>,
+    pub ptr: usize,
+    /// Bytecode instructions executed, one per source command for a program
+    /// compiled without passes; `Compact` and partial evaluation make fewer.
+    pub steps: u64,
+    pub elapsed: Duration,
+    pub termination: Termination,
+}
+
+impl RunResult {
+    pub fn error(message: String) -> RunResult {
+        RunResult {
+            output: Vec::new(),
+            tape: Vec::new(),
+            ptr: 0,
+            steps: 0,
+            elapsed: Duration::from_secs(0),
+            termination: Termination::Error(message),
+        }
+    }
+
+    /// `output` and `tape` are arrays of numbers, the tape cut after its
+    /// last non-zero cell; `text` is the output decoded as UTF-8 and
+    /// `error` is only there for programs that did not compile:
+    ///
+    ///   {"output":[72,105],"text":"Hi","tape":[0,105],"ptr":1,"steps":9,
+    ///    "elapsed_ms":0.01,"termination":"halted"}
+    pub fn to_json(&self) -> String {
+        let used = self.tape.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+        let mut res = format!("{{\"output\":{},\"text\":{},\"tape\":{},\"ptr\":{},\"steps\":{},\"elapsed_ms\":{},\"termination\":{}",
+                              json::bytes(&self.output), json::string(&String::from_utf8_lossy(&self.output)),
+                              json::bytes(&self.tape[..used]), self.ptr, self.steps,
+                              self.elapsed.as_secs_f64() * 1000.0, json::string(self.termination.name()));
+        if let Termination::Error(ref message) = self.termination {
+            res.push_str(&format!(",\"error\":{}", json::string(message)));
+        }
+        res.push('}');
+        res
+    }
+}
+
+/// Passes I/O through, keeping a copy of the output and the last byte read.
+pub struct Recorder<'a> {
+    pub io: &'a mut dyn Io,
+    pub output: Vec<u8>,
+    pub input: Option<u8>,
+}
+
+impl<'a> Recorder<'a> {
+    pub fn new(io: &'a mut dyn Io) -> Recorder<'a> {
+        Recorder { io, output: Vec::new(), input: None }
+    }
+}
+
+impl<'a> Io for Recorder<'a> {
+    fn print(&mut self, val: u8) {
+        self.output.push(val);
+        self.io.print(val);
+    }
+
+    fn read(&mut self) -> u8 {
+        let val = self.io.read();
+        self.input = Some(val);
+        val
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.output.extend_from_slice(bytes);
+        self.io.write(bytes);
+    }
+}
+
+/// Measures `RunResult::elapsed`. wasm32-unknown-unknown has no clock, so
+/// runs there report zero.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub struct Stopwatch(Instant);
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Stopwatch {
+    pub fn start() -> Stopwatch {
+        Stopwatch(Instant::now())
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub struct Stopwatch;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl Stopwatch {
+    pub fn start() -> Stopwatch {
+        Stopwatch
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs(0)
+    }
+}
+
+/// Runs the program like `vm::run`, for at most `fuel` instructions.
+pub fn run(program: &Program, io: &mut dyn Io, fuel: Option<u64>) -> RunResult {
+    let start = Stopwatch::start();
+    let mut machine = Machine::new();
+    let mut io = Recorder::new(io);
+    let mut steps = 0;
+    let fuel = fuel.unwrap_or(u64::MAX);
+    while !machine.halted(program) && steps < fuel {
+        machine.step(program, &mut io);
+        steps += 1;
+    }
+    RunResult {
+        termination: if machine.halted(program) { Termination::Halted } else { Termination::OutOfFuel },
+        output: io.output,
+        tape: machine.tape,
+        ptr: machine.ptr,
+        steps,
+        elapsed: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::iter::Peekable;
+    use std::str::Chars;
+
+    #[derive(PartialEq, Debug)]
+    enum Value {
+        Number(f64),
+        String(String),
+        Array(Vec<Value>),
+        Object(Vec<(String, Value)>),
+    }
+
+    // Just enough JSON for `to_json`: no literals, no whitespace.
+    fn parse(chars: &mut Peekable<Chars>) -> Value {
+        match chars.next() {
+            Some('"') => {
+                let mut s = String::new();
+                loop {
+                    match chars.next().unwrap() {
+                        '"' => return Value::String(s),
+                        '\\' => s.push(match chars.next().unwrap() {
+                            'n' => '\n',
+                            'r' => '\r',
+                            't' => '\t',
+                            'u' => {
+                                let hex: String = chars.by_ref().take(4).collect();
+                                std::char::from_u32(u32::from_str_radix(&hex, 16).unwrap()).unwrap()
+                            }
+                            c => c,
+                        }),
+                        c => s.push(c),
+                    }
+                }
+            }
+            Some('[') => {
+                let mut items = vec![];
+                while chars.peek() != Some(&']') {
+                    items.push(parse(chars));
+                    if chars.peek() == Some(&',') {
+                        chars.next();
+                    }
+                }
+                chars.next();
+                Value::Array(items)
+            }
+            Some('{') => {
+                let mut fields = vec![];
+                while chars.peek() != Some(&'}') {
+                    let key = match parse(chars) {
+                        Value::String(key) => key,
+                        value => panic!("key {:?}", value),
+                    };
+                    assert_eq!(chars.next(), Some(':'));
+                    fields.push((key, parse(chars)));
+                    if chars.peek() == Some(&',') {
+                        chars.next();
+                    }
+                }
+                chars.next();
+                Value::Object(fields)
+            }
+            Some(c) => {
+                let mut number = c.to_string();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit() || **c == '.' || **c == '-' || **c == 'e') {
+                    number.push(c);
+                    chars.next();
+                }
+                Value::Number(number.parse().unwrap())
+            }
+            None => panic!("unexpected end"),
+        }
+    }
+
+    // The `RunResult` a host would rebuild from the JSON.
+    fn from_json(json: &str, tape_len: usize) -> RunResult {
+        let mut chars = json.chars().peekable();
+        let fields = match parse(&mut chars) {
+            Value::Object(fields) => fields,
+            value => panic!("not an object: {:?}", value),
+        };
+        assert_eq!(chars.next(), None);
+        let field = |name| &fields.iter().find(|&(key, _)| key == name).unwrap().1;
+        let number = |name| match *field(name) {
+            Value::Number(n) => n,
+            ref value => panic!("{}: {:?}", name, value),
+        };
+        let bytes = |name| match *field(name) {
+            Value::Array(ref items) => items.iter().map(|item| match *item {
+                Value::Number(n) => n as u8,
+                ref value => panic!("{}: {:?}", name, value),
+            }).collect::<Vec<u8>>(),
+            ref value => panic!("{}: {:?}", name, value),
+        };
+        let string = |name| match *field(name) {
+            Value::String(ref s) => s.clone(),
+            ref value => panic!("{}: {:?}", name, value),
+        };
+        let output = bytes("output");
+        assert_eq!(string("text"), String::from_utf8_lossy(&output));
+        let mut tape = bytes("tape");
+        if tape_len > 0 {
+            tape.resize(tape_len, 0);
+        }
+        RunResult {
+            output,
+            tape,
+            ptr: number("ptr") as usize,
+            steps: number("steps") as u64,
+            elapsed: Duration::from_nanos((number("elapsed_ms") * 1e6).round() as u64),
+            termination: match &string("termination")[..] {
+                "halted" => Termination::Halted,
+                "out-of-fuel" => Termination::OutOfFuel,
+                "error" => Termination::Error(string("error")),
+                name => panic!("termination {}", name),
+            },
+        }
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let mut tape = vec![0; 8];
+        tape[2] = 255;
+        let results = [
+            RunResult {
+                output: b"\"Hi\"\n\\\t\x01 \xff \xe2\x82\xac".to_vec(),
+                tape: tape.clone(),
+                ptr: 7,
+                steps: 1 << 40,
+                elapsed: Duration::from_micros(1500),
+                termination: Termination::Halted,
+            },
+            RunResult {
+                output: vec![],
+                tape: vec![0; 8],
+                ptr: 0,
+                steps: 10,
+                elapsed: Duration::from_secs(0),
+                termination: Termination::OutOfFuel,
+            },
+            RunResult::error("unknown flag '-O9' in \"-O9\"".to_owned()),
+        ];
+        for result in &results {
+            assert_eq!(&from_json(&result.to_json(), result.tape.len()), result);
+        }
+    }
+}
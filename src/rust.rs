@@ -0,0 +1,64 @@
+use brainfuck::*;
+use std::fmt::Write;
+
+fn byte_string(bytes: &[u8]) -> String {
+    let mut result = String::from("b\"");
+    for b in bytes {
+        match *b {
+            b'"' => result.push_str("\\\""),
+            b'\\' => result.push_str("\\\\"),
+            b'\n' => result.push_str("\\n"),
+            b' '..=b'~' => result.push(*b as char),
+            b => write!(result, "\\x{:02x}", b).unwrap(),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn to_rust_block(ops: &[Node], indent: usize, out: &mut String) {
+    let pad = "    ".repeat(indent);
+    for node in ops {
+        match node.op {
+            Op::IncPointer(n) => writeln!(out, "{}p = (p + {}) % TAPE_SIZE;", pad, n % HEAP_SIZE).unwrap(),
+            Op::DecPointer(n) => {
+                writeln!(out, "{}p = (p + TAPE_SIZE - {}) % TAPE_SIZE;", pad, n % HEAP_SIZE).unwrap()
+            }
+            Op::IncVal(n) => writeln!(out, "{}tape[p] = tape[p].wrapping_add({});", pad, n).unwrap(),
+            Op::DecVal(n) => writeln!(out, "{}tape[p] = tape[p].wrapping_sub({});", pad, n).unwrap(),
+            Op::SetRegisterToZero => writeln!(out, "{}tape[p] = 0;", pad).unwrap(),
+            Op::Print => writeln!(out, "{}out.write_all(&[tape[p]]).unwrap();", pad).unwrap(),
+            Op::Read => {
+                writeln!(out, "{}out.flush().unwrap();", pad).unwrap();
+                writeln!(out, "{}tape[p] = match input.next() {{ Some(Ok(b)) => b, _ => 0 }};", pad).unwrap();
+            }
+            Op::InitTape { ref data, ptr } => {
+                writeln!(out, "{}tape[..{}].copy_from_slice(&{:?});", pad, data.len(), data).unwrap();
+                writeln!(out, "{}p = {};", pad, ptr).unwrap();
+            }
+            Op::PrintBytes(ref bytes) => {
+                writeln!(out, "{}out.write_all({}).unwrap();", pad, byte_string(bytes)).unwrap()
+            }
+            Op::While { ref ops } => {
+                writeln!(out, "{}while tape[p] != 0 {{", pad).unwrap();
+                to_rust_block(ops, indent + 1, out);
+                writeln!(out, "{}}}", pad).unwrap();
+            }
+        }
+    }
+}
+
+/// Translates the program to a standalone Rust source file with the
+/// interpreter's semantics: a wrapping pointer and zero on end of input.
+pub fn to_rust(ops: &[Node]) -> String {
+    let mut out = String::new();
+    out.push_str("use std::io::{self, Read, Write};\n\n");
+    writeln!(out, "const TAPE_SIZE: usize = {};\n", HEAP_SIZE).unwrap();
+    out.push_str("#[allow(unused_mut, unused_variables, unused_assignments)]\nfn main() {\n");
+    out.push_str("    let mut tape = [0u8; TAPE_SIZE];\n    let mut p: usize = 0;\n");
+    out.push_str("    let stdin = io::stdin();\n    let mut input = stdin.lock().bytes();\n");
+    out.push_str("    let stdout = io::stdout();\n    let mut out = io::BufWriter::new(stdout.lock());\n");
+    to_rust_block(ops, 1, &mut out);
+    out.push_str("    out.flush().unwrap();\n}\n");
+    out
+}
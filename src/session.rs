@@ -0,0 +1,180 @@
+// The bytecode VM as a state machine that hands control back to its host
+// instead of calling it: hosts feed input whenever they have it and run the
+// program in slices of a given number of instructions.
+use interpreter::Io;
+use run_result::{RunResult, Stopwatch, Termination};
+use snapshot::Snapshot;
+use std::collections::VecDeque;
+use std::mem;
+use vm::{Instr, Machine, Program};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Event {
+    /// The next instruction reads, and there is no input left.
+    NeedsInput,
+    /// Output since the last event, returned before any other event.
+    Output(Vec<u8>),
+    Halted,
+    /// Ran out of instructions for this call.
+    OutOfFuel,
+}
+
+pub struct Session {
+    program: Program,
+    machine: Machine,
+    input: VecDeque<u8>,
+    input_closed: bool,
+    input_pos: u64,
+    output: Vec<u8>,
+    steps: u64,
+}
+
+struct Buffers<'a> {
+    input: &'a mut VecDeque<u8>,
+    output: &'a mut Vec<u8>,
+}
+
+impl<'a> Io for Buffers<'a> {
+    fn print(&mut self, val: u8) {
+        self.output.push(val);
+    }
+
+    // Only called with input available or closed.
+    fn read(&mut self) -> u8 {
+        self.input.pop_front().unwrap_or(0)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.output.extend_from_slice(bytes);
+    }
+}
+
+impl Session {
+    pub fn new(program: Program) -> Session {
+        Session::from_snapshot(program, Snapshot {
+            machine: Machine::new(),
+            steps: 0,
+            input_pos: 0,
+            output: Vec::new(),
+        })
+    }
+
+    /// Continues a paused session; its undelivered output is the first
+    /// event.
+    pub fn from_snapshot(program: Program, snapshot: Snapshot) -> Session {
+        Session {
+            program,
+            machine: snapshot.machine,
+            input: VecDeque::new(),
+            input_closed: false,
+            input_pos: snapshot.input_pos,
+            output: snapshot.output,
+            steps: snapshot.steps,
+        }
+    }
+
+    /// Input provided but not read yet is not part of the snapshot.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            machine: self.machine.clone(),
+            steps: self.steps,
+            input_pos: self.input_pos,
+            output: self.output.clone(),
+        }
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn provide_input(&mut self, bytes: &[u8]) {
+        self.input.extend(bytes);
+    }
+
+    /// Reads past the provided input give 0 instead of `NeedsInput`.
+    pub fn close_input(&mut self) {
+        self.input_closed = true;
+    }
+
+    /// Runs at most `fuel` instructions.
+    pub fn run(&mut self, fuel: u64) -> Event {
+        let mut event = Event::OutOfFuel;
+        for _ in 0..fuel {
+            if self.machine.halted(&self.program) {
+                event = Event::Halted;
+                break;
+            }
+            if self.program.code[self.machine.pc] == Instr::Read {
+                if self.input.is_empty() && !self.input_closed {
+                    event = Event::NeedsInput;
+                    break;
+                }
+                self.input_pos += 1;
+            }
+            let mut io = Buffers { input: &mut self.input, output: &mut self.output };
+            self.machine.step(&self.program, &mut io);
+            self.steps += 1;
+        }
+        if event == Event::OutOfFuel && self.machine.halted(&self.program) {
+            event = Event::Halted;
+        }
+        if self.output.is_empty() {
+            event
+        } else {
+            Event::Output(mem::take(&mut self.output))
+        }
+    }
+}
+
+/// Runs the program to completion, or for at most `fuel` instructions,
+/// on `input`; reads past its end give 0.
+pub fn run_with_input(program: Program, input: &[u8], fuel: Option<u64>) -> RunResult {
+    let start = Stopwatch::start();
+    let mut session = Session::new(program);
+    session.provide_input(input);
+    session.close_input();
+    let mut output = vec![];
+    let termination = loop {
+        match session.run(fuel.unwrap_or(u64::MAX).saturating_sub(session.steps)) {
+            Event::Output(bytes) => output.extend_from_slice(&bytes),
+            Event::Halted => break Termination::Halted,
+            Event::OutOfFuel => break Termination::OutOfFuel,
+            Event::NeedsInput => unreachable!("the input is closed"),
+        }
+    };
+    RunResult {
+        output,
+        tape: session.machine.tape,
+        ptr: session.machine.ptr,
+        steps: session.steps,
+        elapsed: start.elapsed(),
+        termination,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brainfuck::get_ast;
+    use vm;
+
+    fn run(code: &str, input: &[u8], fuel: Option<u64>) -> RunResult {
+        run_with_input(vm::compile(&get_ast(&code.chars().collect::<Vec<_>>()).0), input, fuel)
+    }
+
+    #[test]
+    fn reads_the_input_then_zeros() {
+        let result = run(",>,>,", b"ab", None);
+        assert_eq!((&result.tape[..4], result.ptr, result.steps), (&b"ab\0\0"[..], 2, 5));
+        assert_eq!(result.termination, Termination::Halted);
+        assert_eq!(run(",+.", b"", None).output, [1]);
+    }
+
+    #[test]
+    fn stops_when_the_fuel_runs_out() {
+        let result = run("+[>+<]", b"", Some(100));
+        assert_eq!((result.termination, result.steps, result.tape[1]), (Termination::OutOfFuel, 100, 25));
+        let result = run("+.", b"", Some(2));
+        assert_eq!((result.termination, result.output), (Termination::Halted, vec![1]));
+    }
+}
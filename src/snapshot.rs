@@ -0,0 +1,207 @@
+// A paused bytecode VM as bytes. All numbers are little endian:
+//
+//   magic "BFSNAP", version u16
+//   fingerprint u64   of the program the snapshot belongs to
+//   pc u64, ptr u64, steps u64, input_pos u64
+//   tape length u32, tape
+//   output length u32, output
+//
+// The bytecode is flat, so `pc` is all there is of the loop stack.
+use brainfuck::HEAP_SIZE;
+use byteorder::{ReadBytesExt, WriteBytesExt, LittleEndian};
+use std::io::{Cursor, Read};
+use vm::{Instr, Machine, Program};
+
+const MAGIC: &[u8; 6] = b"BFSNAP";
+const VERSION: u16 = 1;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Snapshot {
+    pub machine: Machine,
+    /// Instructions run before the snapshot.
+    pub steps: u64,
+    /// Input bytes the program has read.
+    pub input_pos: u64,
+    /// Output the host had not delivered yet.
+    pub output: Vec<u8>,
+}
+
+// FNV-1a over the instructions and their constants.
+fn fingerprint(program: &Program) -> u64 {
+    let mut bytes = vec![];
+    for instr in &program.code {
+        let (tag, operand) = match *instr {
+            Instr::Right(n) => (0, n),
+            Instr::Left(n) => (1, n),
+            Instr::Add(n) => (2, n as usize),
+            Instr::Sub(n) => (3, n as usize),
+            Instr::Zero => (4, 0),
+            Instr::Print => (5, 0),
+            Instr::Read => (6, 0),
+            Instr::InitTape(i) => (7, i),
+            Instr::PrintBytes(i) => (8, i),
+            Instr::JumpIfZero(target) => (9, target),
+            Instr::JumpIfNotZero(target) => (10, target),
+        };
+        bytes.push(tag);
+        bytes.write_u64::<LittleEndian>(operand as u64).unwrap();
+    }
+    for &(ref data, ptr) in &program.tapes {
+        bytes.extend_from_slice(data);
+        bytes.write_u64::<LittleEndian>(ptr as u64).unwrap();
+    }
+    for string in &program.strings {
+        bytes.extend_from_slice(string);
+    }
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x100_0000_01b3))
+}
+
+impl Snapshot {
+    pub fn to_bytes(&self, program: &Program) -> Vec<u8> {
+        let mut vec = MAGIC.to_vec();
+        vec.write_u16::<LittleEndian>(VERSION).unwrap();
+        vec.write_u64::<LittleEndian>(fingerprint(program)).unwrap();
+        vec.write_u64::<LittleEndian>(self.machine.pc as u64).unwrap();
+        vec.write_u64::<LittleEndian>(self.machine.ptr as u64).unwrap();
+        vec.write_u64::<LittleEndian>(self.steps).unwrap();
+        vec.write_u64::<LittleEndian>(self.input_pos).unwrap();
+        vec.write_u32::<LittleEndian>(self.machine.tape.len() as u32).unwrap();
+        vec.extend_from_slice(&self.machine.tape);
+        vec.write_u32::<LittleEndian>(self.output.len() as u32).unwrap();
+        vec.extend_from_slice(&self.output);
+        vec
+    }
+
+    /// Fails unless `bytes` is a snapshot of `program`.
+    pub fn from_bytes(bytes: &[u8], program: &Program) -> Result<Snapshot, String> {
+        let truncated = |_| "truncated snapshot".to_owned();
+        let mut cursor = Cursor::new(bytes);
+        let mut magic = [0; 6];
+        cursor.read_exact(&mut magic).map_err(truncated)?;
+        if &magic != MAGIC {
+            return Err("not a snapshot".to_owned());
+        }
+        let version = cursor.read_u16::<LittleEndian>().map_err(truncated)?;
+        if version != VERSION {
+            return Err(format!("unsupported snapshot version {}", version));
+        }
+        if cursor.read_u64::<LittleEndian>().map_err(truncated)? != fingerprint(program) {
+            return Err("snapshot of a different program or optimisation flags".to_owned());
+        }
+        let pc = cursor.read_u64::<LittleEndian>().map_err(truncated)? as usize;
+        let ptr = cursor.read_u64::<LittleEndian>().map_err(truncated)? as usize;
+        let steps = cursor.read_u64::<LittleEndian>().map_err(truncated)?;
+        let input_pos = cursor.read_u64::<LittleEndian>().map_err(truncated)?;
+        let tape_len = cursor.read_u32::<LittleEndian>().map_err(truncated)? as usize;
+        if tape_len != HEAP_SIZE || ptr >= HEAP_SIZE || pc > program.code.len() {
+            return Err("corrupt snapshot".to_owned());
+        }
+        let mut tape = vec![0; tape_len];
+        cursor.read_exact(&mut tape).map_err(truncated)?;
+        let output_len = cursor.read_u32::<LittleEndian>().map_err(truncated)? as usize;
+        if output_len > bytes.len() {
+            return Err("truncated snapshot".to_owned());
+        }
+        let mut output = vec![0; output_len];
+        cursor.read_exact(&mut output).map_err(truncated)?;
+        if cursor.position() != bytes.len() as u64 {
+            return Err("corrupt snapshot".to_owned());
+        }
+        Ok(Snapshot {
+            machine: Machine { tape, ptr, pc },
+            steps,
+            input_pos,
+            output,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brainfuck::get_ast;
+    use session::{Event, Session};
+    use vm;
+
+    // Echoes its input shifted by one until a 0 byte.
+    const PROGRAM: &str = ",[+.,]";
+    const INPUT: &[u8] = b"HAL";
+
+    fn program() -> Program {
+        vm::compile(&get_ast(&PROGRAM.chars().collect::<Vec<_>>()).0)
+    }
+
+    // Runs the session to the end on what is left of `INPUT`.
+    fn finish(mut session: Session, output: &mut Vec<u8>) {
+        let read = (session.snapshot().input_pos as usize).min(INPUT.len());
+        session.provide_input(&INPUT[read..]);
+        session.close_input();
+        loop {
+            match session.run(u64::MAX) {
+                Event::Output(bytes) => output.extend_from_slice(&bytes),
+                Event::Halted => return,
+                event => panic!("unexpected {:?}", event),
+            }
+        }
+    }
+
+    fn snapshot_after(steps: u64) -> (Vec<u8>, Vec<u8>) {
+        let mut session = Session::new(program());
+        session.provide_input(INPUT);
+        session.close_input();
+        let mut output = vec![];
+        while session.snapshot().steps < steps {
+            match session.run(steps - session.snapshot().steps) {
+                Event::Output(bytes) => output.extend_from_slice(&bytes),
+                _ => break,
+            }
+        }
+        (session.snapshot().to_bytes(session.program()), output)
+    }
+
+    #[test]
+    fn resumed_runs_match_uninterrupted_ones() {
+        let mut uninterrupted = vec![];
+        finish(Session::new(program()), &mut uninterrupted);
+        assert_eq!(uninterrupted, b"IBM");
+        for steps in 0..20 {
+            let (bytes, mut output) = snapshot_after(steps);
+            let program = program();
+            let snapshot = Snapshot::from_bytes(&bytes, &program).unwrap();
+            assert_eq!(snapshot.to_bytes(&program), bytes);
+            finish(Session::from_snapshot(program, snapshot), &mut output);
+            assert_eq!(output, uninterrupted, "resumed after {} steps", steps);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_snapshots() {
+        let (bytes, _) = snapshot_after(5);
+        for len in 0..bytes.len() {
+            assert_eq!(Snapshot::from_bytes(&bytes[..len], &program()), Err("truncated snapshot".to_owned()), "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn rejects_corrupted_snapshots() {
+        let (bytes, _) = snapshot_after(5);
+        let corrupt = |offset: usize, byte: u8| {
+            let mut bytes = bytes.clone();
+            bytes[offset] = byte;
+            Snapshot::from_bytes(&bytes, &program()).unwrap_err()
+        };
+        assert_eq!(corrupt(0, b'X'), "not a snapshot");
+        assert_eq!(corrupt(6, 2), "unsupported snapshot version 2");
+        assert_eq!(corrupt(8, bytes[8] ^ 1), "snapshot of a different program or optimisation flags");
+        // pc, ptr and the tape length
+        assert_eq!(corrupt(16 + 7, 1), "corrupt snapshot");
+        assert_eq!(corrupt(24 + 7, 1), "corrupt snapshot");
+        assert_eq!(corrupt(48, 1), "corrupt snapshot");
+
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert_eq!(Snapshot::from_bytes(&longer, &program()), Err("corrupt snapshot".to_owned()));
+        let other = vm::compile(&get_ast(&",[-.,]".chars().collect::<Vec<_>>()).0);
+        assert_eq!(Snapshot::from_bytes(&bytes, &other), Err("snapshot of a different program or optimisation flags".to_owned()));
+    }
+}
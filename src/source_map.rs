@@ -0,0 +1,70 @@
+// Source map v3, see https://sourcemaps.info/spec.html
+use brainfuck::Span;
+use json;
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn encode_vlq(val: isize, out: &mut String) {
+    let mut val = if val < 0 { ((-val) << 1) | 1 } else { val << 1 };
+    loop {
+        let mut digit = val & 0b1_1111;
+        val >>= 5;
+        if val > 0 {
+            digit |= 0b10_0000;
+        }
+        out.push(BASE64[digit as usize] as char);
+        if val == 0 {
+            return;
+        }
+    }
+}
+
+// Zero based (line, column) of every char in `source`.
+fn positions(source: &str) -> Vec<(usize, usize)> {
+    let mut line = 0;
+    let mut column = 0;
+    let mut result = Vec::new();
+    for ch in source.chars() {
+        result.push((line, column));
+        if ch == '\n' {
+            line += 1;
+            column = 0;
+        } else {
+            column += 1;
+        }
+    }
+    result
+}
+
+/// Builds a source map for a wasm module: `mappings` pairs byte offsets in
+/// the module with the span of `source` the code there was compiled from.
+pub fn source_map(source: &str, file: &str, mappings: &[(usize, Span)]) -> String {
+    let positions = positions(source);
+    let mut encoded = String::new();
+    let mut prev_offset = 0;
+    let mut prev_line = 0;
+    let mut prev_column = 0;
+    let mut mappings = mappings.to_vec();
+    mappings.sort_by_key(|&(offset, _)| offset);
+    mappings.dedup_by_key(|&mut (offset, _)| offset);
+
+    for (i, &(offset, (start, _))) in mappings.iter().enumerate() {
+        let (line, column) = match positions.get(start) {
+            Some(pos) => *pos,
+            None => continue,
+        };
+        if i > 0 {
+            encoded.push(',');
+        }
+        encode_vlq(offset as isize - prev_offset as isize, &mut encoded);
+        encode_vlq(0, &mut encoded);
+        encode_vlq(line as isize - prev_line as isize, &mut encoded);
+        encode_vlq(column as isize - prev_column as isize, &mut encoded);
+        prev_offset = offset;
+        prev_line = line;
+        prev_column = column;
+    }
+
+    format!("{{\"version\":3,\"sources\":[{}],\"sourcesContent\":[{}],\"names\":[],\"mappings\":{}}}",
+            json::string(file), json::string(source), json::string(&encoded))
+}
@@ -0,0 +1,114 @@
+// Runs a program on the bytecode VM and logs the instructions it executes
+// as JSON lines, one object per instruction:
+//
+//   {"step":41,"pc":7,"span":[9,10],"ptr":1,"before":3,"after":4}
+//
+// `step` counts every instruction run so far, traced or not, `pc` indexes
+// `vm::Program::code` and `span` is the range of source chars the
+// instruction came from. `ptr` is the pointer before the instruction and
+// `before`/`after` the value of the cell it points at. Instructions that
+// print add `"out":[bytes]`, a read adds `"in":byte`.
+use brainfuck::*;
+use interpreter::Io;
+use json;
+use run_result::Recorder;
+use std::io::{self, Write};
+use vm::{Machine, Program};
+
+pub struct Options {
+    /// Only instructions whose span overlaps this range of source chars.
+    pub range: Option<Span>,
+    /// Log every `sample`th instruction that passes the range filter.
+    pub sample: u64,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options { range: None, sample: 1 }
+    }
+}
+
+fn overlaps(a: Span, b: Span) -> bool {
+    a.0 < b.1 && b.0 < a.1
+}
+
+/// Runs the program like `vm::run`, writing the trace to `out`.
+pub fn run(program: &Program, io: &mut dyn Io, options: &Options, out: &mut dyn Write) -> io::Result<()> {
+    let mut machine = Machine::new();
+    let mut io = Recorder::new(io);
+    let mut step = 0u64;
+    let mut matched = 0u64;
+    while !machine.halted(program) {
+        let (pc, ptr) = (machine.pc, machine.ptr);
+        let span = program.spans[pc];
+        let in_range = options.range.is_none_or(|range| overlaps(range, span));
+        if in_range {
+            matched += 1;
+        }
+        if !in_range || !(matched - 1).is_multiple_of(options.sample.max(1)) {
+            machine.step(program, &mut *io.io);
+            step += 1;
+            continue;
+        }
+        let before = machine.tape[ptr];
+        machine.step(program, &mut io);
+        write!(out, "{{\"step\":{},\"pc\":{},\"span\":[{},{}],\"ptr\":{},\"before\":{},\"after\":{}",
+               step, pc, span.0, span.1, ptr, before, machine.tape[ptr])?;
+        if !io.output.is_empty() {
+            write!(out, ",\"out\":{}", json::bytes(&io.output))?;
+            io.output.clear();
+        }
+        if let Some(val) = io.input.take() {
+            write!(out, ",\"in\":{}", val)?;
+        }
+        writeln!(out, "}}")?;
+        step += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vm;
+
+    struct Echo(Vec<u8>);
+
+    impl Io for Echo {
+        fn print(&mut self, val: u8) {
+            self.0.push(val);
+        }
+
+        fn read(&mut self) -> u8 {
+            b'A'
+        }
+    }
+
+    fn trace(code: &str, options: &Options) -> Vec<String> {
+        let (ast, _) = get_ast(&code.chars().collect::<Vec<_>>());
+        let mut out = Vec::new();
+        run(&vm::compile(&compact(&ast)), &mut Echo(vec![]), options, &mut out).unwrap();
+        String::from_utf8(out).unwrap().lines().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn logs_each_instruction_with_its_io() {
+        assert_eq!(trace(",+++.", &Options::default()), [
+            r#"{"step":0,"pc":0,"span":[0,1],"ptr":0,"before":0,"after":65,"in":65}"#,
+            r#"{"step":1,"pc":1,"span":[1,4],"ptr":0,"before":65,"after":68}"#,
+            r#"{"step":2,"pc":2,"span":[4,5],"ptr":0,"before":68,"after":68,"out":[68]}"#,
+        ]);
+    }
+
+    #[test]
+    fn filters_by_range_and_sample() {
+        // the jumps span the whole loop, so every other one is logged
+        let lines = trace("+++[>+<-]", &Options { range: Some((5, 6)), sample: 2 });
+        assert_eq!(lines, [
+            r#"{"step":1,"pc":1,"span":[3,9],"ptr":0,"before":3,"after":3}"#,
+            r#"{"step":6,"pc":6,"span":[3,9],"ptr":0,"before":2,"after":2}"#,
+            r#"{"step":11,"pc":6,"span":[3,9],"ptr":0,"before":1,"after":1}"#,
+            r#"{"step":16,"pc":6,"span":[3,9],"ptr":0,"before":0,"after":0}"#,
+        ]);
+    }
+}
@@ -0,0 +1,173 @@
+// A flat bytecode for the `Op` tree. Loops become a pair of conditional
+// jumps with precomputed targets, so running a program needs neither
+// recursion nor a walk over nested vectors.
+use brainfuck::*;
+use interpreter::Io;
+use std::mem;
+use std::slice;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Instr {
+    Right(usize),
+    Left(usize),
+    Add(u8),
+    Sub(u8),
+    Zero,
+    Print,
+    Read,
+    /// Index into `Program::tapes`.
+    InitTape(usize),
+    /// Index into `Program::strings`.
+    PrintBytes(usize),
+    /// Start of a loop, jumps past its end when the cell is zero.
+    JumpIfZero(usize),
+    /// End of a loop, jumps back to the start of its body unless the cell
+    /// is zero.
+    JumpIfNotZero(usize),
+}
+
+pub struct Program {
+    pub code: Vec<Instr>,
+    /// Source of every instruction in `code`.
+    pub spans: Vec<Span>,
+    pub tapes: Vec<(Vec<u8>, usize)>,
+    pub strings: Vec<Vec<u8>>,
+}
+
+pub fn compile(ops: &[Node]) -> Program {
+    let mut program = Program {
+        code: Vec::new(),
+        spans: Vec::new(),
+        tapes: Vec::new(),
+        strings: Vec::new(),
+    };
+    // the loops being compiled, innermost last: the rest of the list around
+    // each, where it starts and its span
+    let mut loops: Vec<(slice::Iter<Node>, usize, Span)> = Vec::new();
+    let mut nodes = ops.iter();
+    loop {
+        let node = match nodes.next() {
+            Some(node) => node,
+            None => match loops.pop() {
+                Some((outer, start, span)) => {
+                    let end = program.code.len();
+                    program.code[start] = Instr::JumpIfZero(end + 1);
+                    program.code.push(Instr::JumpIfNotZero(start + 1));
+                    program.spans.push(span);
+                    nodes = outer;
+                    continue;
+                }
+                None => return program,
+            },
+        };
+        let instr = match node.op {
+            Op::IncPointer(n) => Instr::Right(n % HEAP_SIZE),
+            Op::DecPointer(n) => Instr::Left(n % HEAP_SIZE),
+            Op::IncVal(n) => Instr::Add(n),
+            Op::DecVal(n) => Instr::Sub(n),
+            Op::SetRegisterToZero => Instr::Zero,
+            Op::Print => Instr::Print,
+            Op::Read => Instr::Read,
+            Op::InitTape { ref data, ptr } => {
+                program.tapes.push((data.clone(), ptr));
+                Instr::InitTape(program.tapes.len() - 1)
+            }
+            Op::PrintBytes(ref bytes) => {
+                program.strings.push(bytes.clone());
+                Instr::PrintBytes(program.strings.len() - 1)
+            }
+            Op::While { ref ops } => {
+                loops.push((mem::replace(&mut nodes, ops.iter()), program.code.len(), node.span));
+                program.code.push(Instr::JumpIfZero(0));
+                program.spans.push(node.span);
+                continue;
+            }
+        };
+        program.code.push(instr);
+        program.spans.push(node.span);
+    }
+}
+
+/// A program paused between two instructions.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Machine {
+    pub tape: Vec<u8>,
+    pub ptr: usize,
+    /// Index into `Program::code` of the next instruction.
+    pub pc: usize,
+}
+
+impl Machine {
+    /// Zeroed tape, paused before the first instruction.
+    pub fn new() -> Machine {
+        Machine {
+            tape: vec![0; HEAP_SIZE],
+            ptr: 0,
+            pc: 0,
+        }
+    }
+
+    pub fn halted(&self, program: &Program) -> bool {
+        self.pc >= program.code.len()
+    }
+
+    /// Executes the instruction at `pc`, which must not be past the end.
+    #[inline]
+    pub fn step(&mut self, program: &Program, io: &mut dyn Io) {
+        let tape = &mut self.tape;
+        let ptr = self.ptr;
+        match program.code[self.pc] {
+            Instr::Right(n) => {
+                self.ptr += n;
+                if self.ptr >= HEAP_SIZE {
+                    self.ptr -= HEAP_SIZE;
+                }
+            }
+            Instr::Left(n) => {
+                if ptr < n {
+                    self.ptr += HEAP_SIZE;
+                }
+                self.ptr -= n;
+            }
+            Instr::Add(n) => tape[ptr] = tape[ptr].wrapping_add(n),
+            Instr::Sub(n) => tape[ptr] = tape[ptr].wrapping_sub(n),
+            Instr::Zero => tape[ptr] = 0,
+            Instr::Print => io.print(tape[ptr]),
+            Instr::Read => tape[ptr] = io.read(),
+            Instr::InitTape(i) => {
+                let (ref data, p) = program.tapes[i];
+                tape[..data.len()].copy_from_slice(data);
+                self.ptr = p;
+            }
+            Instr::PrintBytes(i) => io.write(&program.strings[i]),
+            Instr::JumpIfZero(target) => {
+                if tape[ptr] == 0 {
+                    self.pc = target;
+                    return;
+                }
+            }
+            Instr::JumpIfNotZero(target) => {
+                if tape[ptr] != 0 {
+                    self.pc = target;
+                    return;
+                }
+            }
+        }
+        self.pc += 1;
+    }
+}
+
+impl Default for Machine {
+    fn default() -> Machine {
+        Machine::new()
+    }
+}
+
+/// Runs the program on a zeroed tape, with the same semantics as
+/// `interpreter::run`.
+pub fn run(program: &Program, io: &mut dyn Io) {
+    let mut machine = Machine::new();
+    while !machine.halted(program) {
+        machine.step(program, io);
+    }
+}
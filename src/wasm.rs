@@ -2,11 +2,15 @@
 use byteorder::{WriteBytesExt, LittleEndian};
 
 use brainfuck::*;
+use coverage::Point;
 use std::fmt;
 use leb128;
+use source_map;
+use passes::{Pass, PassManager};
 
 const EXTERNAL_CALL_PRINT:u8 = 0;
 const EXTERNAL_CALL_READ:u8 = 1;
+const EXTERNAL_CALL_WRITE:u8 = 2;
 
 const FUNC:u8 = 0x60;
 const I32:u8 = 0x7f;
@@ -32,10 +36,10 @@ const WASM_VERSION:u32 = 0x1;
 
 type Position = u8;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 enum Wast {
     Call(u8), //should be LEB128
-    I32Const(u8), //should be LEB128
+    I32Const(i32),
     I32Store8,
     I32Load8u,
     I32Eqz,
@@ -49,6 +53,8 @@ enum Wast {
     End,
     Br(u8),
     BrIf(u8),
+    /// Emits no code, the following instructions come from `Span`.
+    Mark(Span),
 }
 
 impl Wast {
@@ -77,7 +83,7 @@ impl Wast {
             Wast::End => vec.write_u8(END).unwrap(),
             Wast::I32Const(n) => {
                 vec.write_u8(I32_CONST).unwrap();
-                vec.write_u8(n).unwrap();
+                vec.append(&mut leb128::encode_signed(n));
             },
             Wast::SetLocal(n) => {
                 vec.write_u8(SET_LOCAL).unwrap();
@@ -103,12 +109,13 @@ impl Wast {
                 vec.write_u8(BRIF).unwrap();
                 vec.write_u8(n).unwrap();
             },
+            Wast::Mark(_) => (),
         }
     }
 }
 
 
-fn simple_optimasation(code:&Vec<Wast>) -> Vec<Wast> {
+fn simple_optimasation(code:&[Wast]) -> Vec<Wast> {
     let mut r = vec![];
 
     let mut i = 0;
@@ -116,10 +123,16 @@ fn simple_optimasation(code:&Vec<Wast>) -> Vec<Wast> {
     while i < len {
         let op =  if i + 1 < len {
             match (&code[i], &code[i+1]) {
-                (&Wast::SetLocal(ref a), &Wast::GetLocal(ref b)) if a == b =>
+                (Wast::SetLocal(a), Wast::GetLocal(b)) if a == b =>
                     {
                         i+=1;
-                        Wast::TeeLocal(a.clone())
+                        Wast::TeeLocal(*a)
+                    },
+                (Wast::SetLocal(a), Wast::Mark(span)) if i + 2 < len && code[i+2] == Wast::GetLocal(*a) =>
+                    {
+                        r.push(Wast::TeeLocal(*a));
+                        i+=2;
+                        Wast::Mark(*span)
                     },
                 (w, _) => w.clone()
             }
@@ -160,26 +173,33 @@ impl TypeDef {
 }
 
 type Imports = Vec<(Name, Name, TypeDef)>;
-type Functions = Vec<(Name, TypeDef, NumberOfI32, Vec<Wast>)>;
+// name, signature, names of the i32 locals and body
+type Functions = Vec<(Name, TypeDef, Vec<Name>, Vec<Wast>)>;
+// active segments: memory offset and the bytes stored there
+type Data = Vec<(u32, Vec<u8>)>;
 
 struct Module {
+    name: Name,
     imports: Imports,
-    functions: Functions
+    functions: Functions,
+    data: Data,
+    custom: Vec<(Name, Vec<u8>)>
 }
 
 impl Module {
-    fn to_binary (&self, vec: &mut Vec<u8>) {
+    // Returns the module offsets of the `Wast::Mark`s in the code.
+    fn to_binary (&self, vec: &mut Vec<u8>) -> Vec<(usize, Span)> {
         fn types_section (module: &Module, vec: &mut Vec<u8>){
             let elements = (module.functions.len() + module.imports.len()) as u8;
             if elements > 0 {
 
                 let mut td_vec = vec![];
                 td_vec.write_u8(elements).unwrap();
-                for &(_, _, ref td) in &module.imports {
+                for (_, _, td) in &module.imports {
                     td.to_binary(&mut td_vec);
                 }
 
-                for &(_, ref td, _, _) in &module.functions {
+                for (_, td, _, _) in &module.functions {
                     td.to_binary(&mut td_vec);
                 }
                 const TYPES_SECTION : u8 = 1;
@@ -194,8 +214,8 @@ impl Module {
             vec.append(&mut n);
         }
 
-        fn append_wasm_string(s:&String, vec: &mut Vec<u8>) {
-            let mut bytes = s.clone().into_bytes();
+        fn append_wasm_string(s:&str, vec: &mut Vec<u8>) {
+            let mut bytes = s.as_bytes().to_vec();
             write_leb128(bytes.len() as u32,  vec);
             vec.append(&mut bytes);
         }
@@ -206,14 +226,11 @@ impl Module {
                 let mut ims_vec = vec![];
                 ims_vec.write_u8(elements).unwrap();
             
-                let mut i = 0;
-                
-                for &(ref module_str, ref field_str, _) in imports {
+                for (i, (module_str, field_str, _)) in imports.iter().enumerate() {
                     append_wasm_string(module_str, &mut ims_vec);
                     append_wasm_string(field_str, &mut ims_vec);
                     ims_vec.write_u8(0).unwrap();//kind
-                    ims_vec.write_u8(i).unwrap();//signature
-                    i+=1;
+                    ims_vec.write_u8(i as u8).unwrap();//signature
                 }
 
                 const IMPORTS_SECTION : u8 = 2;
@@ -229,11 +246,8 @@ impl Module {
                 let mut fns_vec = vec![];
                 fns_vec.write_u8(elements).unwrap();
             
-                let mut i = imports_no;
-                
-                for _ in functions {
+                for (i, _) in (imports_no..).zip(functions.iter()) {
                     fns_vec.write_u8(i).unwrap();
-                    i+=1;
                 }
 
                 const FUNCTIONS_SECION : u8 = 3;
@@ -244,19 +258,19 @@ impl Module {
         }
 
         fn exports_section (functions: &Functions, no_imports:u8, vec: &mut Vec<u8>){
-            let elements = functions.len() as u8;
+            let elements = functions.len() as u8 + 1; //functions and memory
             if elements > 0 {
                 let mut fns_vec = vec![];
                 fns_vec.write_u8(elements).unwrap();
             
-                let mut i = no_imports;
-                
-                for &(ref name, _, _, _) in functions {
+                for (i, (name, _, _, _)) in (no_imports..).zip(functions.iter()) {
                     append_wasm_string(name, &mut fns_vec);
                     fns_vec.write_u8(0).unwrap();//kind
                     fns_vec.write_u8(i).unwrap();//signature
-                    i+=1;
                 }
+                append_wasm_string("memory", &mut fns_vec);
+                fns_vec.write_u8(2).unwrap();//kind
+                fns_vec.write_u8(0).unwrap();//memory index
 
                 const EXPORTS_SECTION : u8 = 7;
                 vec.write_u8(EXPORTS_SECTION).unwrap();
@@ -279,35 +293,130 @@ impl Module {
             vec.append(&mut fns_vec);
         }
 
-        fn code_section (functions: &Functions, vec: &mut Vec<u8>){
+        // Also records at which byte of the module each `Wast::Mark` ended up.
+        fn code_section (functions: &Functions, vec: &mut Vec<u8>, marks: &mut Vec<(usize, Span)>){
             let elements = functions.len() as u8;
             if elements > 0 {
                 let mut fns_vec = vec![];
                 fns_vec.write_u8(elements).unwrap();
             
-                for &(_, _, ref local_vars, ref wasmt) in functions {
+                let mut fn_marks = vec![];
+                for (_, _, local_vars, wasmt) in functions {
                     let mut code = vec![];
 
-                    code.write_u8(*local_vars).unwrap();
-                    for _ in 0..*local_vars {
+                    code.write_u8(local_vars.len() as u8).unwrap();
+                    for _ in local_vars {
                         code.write_u8(1).unwrap(); //type
                         code.write_u8(I32).unwrap();
                     }
+                    let mut code_marks = vec![];
                     for w in wasmt {
+                        if let Wast::Mark(span) = *w {
+                            code_marks.push((code.len(), span));
+                        }
                         w.to_binary(&mut code);
                     }
                     code.write_u8(END).unwrap();
                     write_leb128(code.len() as u32, &mut fns_vec);
+                    let base = fns_vec.len();
+                    fn_marks.extend(code_marks.into_iter().map(|(offset, span)| (base + offset, span)));
                     fns_vec.append(&mut code);
                     
                 }
                 const CODE_SECTION : u8 = 10;
                 vec.write_u8(CODE_SECTION).unwrap();
                 write_leb128(fns_vec.len() as u32, vec);
+                let base = vec.len();
+                marks.extend(fn_marks.into_iter().map(|(offset, span)| (base + offset, span)));
                 vec.append(&mut fns_vec);
             }
         }
 
+        fn data_count_section (data: &Data, vec: &mut Vec<u8>){
+            if !data.is_empty() {
+                let mut count_vec = vec![];
+                write_leb128(data.len() as u32, &mut count_vec);
+
+                const DATA_COUNT_SECTION : u8 = 12;
+                vec.write_u8(DATA_COUNT_SECTION).unwrap();
+                write_leb128(count_vec.len() as u32, vec);
+                vec.append(&mut count_vec);
+            }
+        }
+
+        fn data_section (data: &Data, vec: &mut Vec<u8>){
+            if !data.is_empty() {
+                let mut data_vec = vec![];
+                write_leb128(data.len() as u32, &mut data_vec);
+
+                for (offset, bytes) in data {
+                    data_vec.write_u8(0).unwrap(); //active, memory 0
+                    Wast::I32Const(*offset as i32).to_binary(&mut data_vec);
+                    data_vec.write_u8(END).unwrap();
+                    write_leb128(bytes.len() as u32, &mut data_vec);
+                    data_vec.extend_from_slice(bytes);
+                }
+
+                const DATA_SECTION : u8 = 11;
+                vec.write_u8(DATA_SECTION).unwrap();
+                write_leb128(data_vec.len() as u32, vec);
+                vec.append(&mut data_vec);
+            }
+        }
+
+        fn custom_section (name: &str, payload: &[u8], vec: &mut Vec<u8>){
+            let mut custom_vec = vec![];
+            append_wasm_string(name, &mut custom_vec);
+            custom_vec.extend_from_slice(payload);
+
+            const CUSTOM_SECTION : u8 = 0;
+            vec.write_u8(CUSTOM_SECTION).unwrap();
+            write_leb128(custom_vec.len() as u32, vec);
+            vec.append(&mut custom_vec);
+        }
+
+        fn name_section (module: &Module, vec: &mut Vec<u8>){
+            fn subsection (id: u8, mut content: Vec<u8>, vec: &mut Vec<u8>) {
+                vec.write_u8(id).unwrap();
+                write_leb128(content.len() as u32, vec);
+                vec.append(&mut content);
+            }
+
+            let mut names_vec = vec![];
+
+            let mut module_name = vec![];
+            append_wasm_string(&module.name, &mut module_name);
+            const MODULE_NAME : u8 = 0;
+            subsection(MODULE_NAME, module_name, &mut names_vec);
+
+            let mut function_names = vec![];
+            write_leb128((module.imports.len() + module.functions.len()) as u32, &mut function_names);
+            let import_names = module.imports.iter().map(|(_, field, _)| field);
+            let fn_names = module.functions.iter().map(|(name, _, _, _)| name);
+            for (i, name) in import_names.chain(fn_names).enumerate() {
+                write_leb128(i as u32, &mut function_names);
+                append_wasm_string(name, &mut function_names);
+            }
+            const FUNCTION_NAMES : u8 = 1;
+            subsection(FUNCTION_NAMES, function_names, &mut names_vec);
+
+            let mut local_names = vec![];
+            write_leb128(module.functions.len() as u32, &mut local_names);
+            for (i, (_, td, locals, _)) in module.functions.iter().enumerate() {
+                write_leb128((module.imports.len() + i) as u32, &mut local_names);
+                write_leb128(locals.len() as u32, &mut local_names);
+                for (j, name) in locals.iter().enumerate() {
+                    // locals are numbered after the parameters
+                    write_leb128(u32::from(td.params) + j as u32, &mut local_names);
+                    append_wasm_string(name, &mut local_names);
+                }
+            }
+            const LOCAL_NAMES : u8 = 2;
+            subsection(LOCAL_NAMES, local_names, &mut names_vec);
+
+            custom_section("name", &names_vec, vec);
+        }
+
         vec.write_u32::<LittleEndian>(WASM_MAGIC).unwrap();
         vec.write_u32::<LittleEndian>(WASM_VERSION).unwrap();
 
@@ -316,7 +425,15 @@ impl Module {
         functions_section(&self.functions, self.imports.len() as u8, vec);
         memory_section(vec);
         exports_section(&self.functions, self.imports.len() as u8, vec);
-        code_section(&self.functions, vec);
+        data_count_section(&self.data, vec);
+        let mut marks = vec![];
+        code_section(&self.functions, vec, &mut marks);
+        data_section(&self.data, vec);
+        name_section(self, vec);
+        for (name, payload) in &self.custom {
+            custom_section(name, payload, vec);
+        }
+        marks
     }
 }
 
@@ -338,21 +455,52 @@ impl fmt::Display for Wast {
             Wast::BrIf (i) => write!(f, "br_if {}", i),
             Wast::I32Eqz => write!(f, "i32.eqz"),
             Wast::Call(i) => write!(f, "call {}", i),
+            Wast::Mark((start, end)) => write!(f, ";; {}..{}", start, end),
         }
     }
 }
 
-fn to_wasmt (ops: &Op, res : &mut Vec<Wast>) {
-    match *ops {
+// One byte per point, set to 1 once the point runs.
+struct Coverage {
+    base: u32,
+    points: Vec<Point>,
+}
+
+fn cover (coverage: &mut Option<Coverage>, point: Point, res: &mut Vec<Wast>) {
+    if let Some(ref mut coverage) = *coverage {
+        res.push(Wast::I32Const((coverage.base + coverage.points.len() as u32) as i32));
+        res.push(Wast::I32Const(1));
+        res.push(Wast::I32Store8);
+        coverage.points.push(point);
+    }
+}
+
+// Where the constant data `to_wasmt` places after the tape ends.
+fn data_end (ops: &[Node]) -> usize {
+    ops.iter().map(|node| match node.op {
+        Op::PrintBytes(ref bytes) => bytes.len(),
+        Op::While { ref ops } => data_end(ops) - HEAP_SIZE,
+        _ => 0,
+    }).sum::<usize>() + HEAP_SIZE
+}
+
+// Constant data is placed in memory right after the tape.
+fn to_wasmt (node: &Node, res : &mut Vec<Wast>, data: &mut Data, coverage: &mut Option<Coverage>) {
+    res.push(Wast::Mark(node.span));
+    match node.op {
+        Op::While { .. } => cover(coverage, Point::Op((node.span.0, node.span.0 + 1)), res),
+        _ => cover(coverage, Point::Op(node.span), res),
+    }
+    match node.op {
         Op::IncPointer(n) => {
             res.push(Wast::GetLocal(0));
-            res.push(Wast::I32Const(n as u8));
+            res.push(Wast::I32Const(n as i32));
             res.push(Wast::I32Add);
             res.push(Wast::SetLocal(0));
         },
         Op::DecPointer(n) => {
             res.push(Wast::GetLocal(0));
-            res.push(Wast::I32Const(n as u8));
+            res.push(Wast::I32Const(n as i32));
             res.push(Wast::I32Sub);
             res.push(Wast::SetLocal(0));
         },
@@ -361,7 +509,7 @@ fn to_wasmt (ops: &Op, res : &mut Vec<Wast>) {
 
             res.push(Wast::GetLocal(0));            
             res.push(Wast::I32Load8u);
-            res.push(Wast::I32Const(n as u8));
+            res.push(Wast::I32Const(i32::from(n)));
             res.push(Wast::I32Add);
             res.push(Wast::I32Store8);
         },
@@ -369,7 +517,7 @@ fn to_wasmt (ops: &Op, res : &mut Vec<Wast>) {
             res.push(Wast::GetLocal(0));                        
             res.push(Wast::GetLocal(0));            
             res.push(Wast::I32Load8u);
-            res.push(Wast::I32Const(n as u8));
+            res.push(Wast::I32Const(i32::from(n)));
             res.push(Wast::I32Sub);
             res.push(Wast::I32Store8);
         },
@@ -388,6 +536,20 @@ fn to_wasmt (ops: &Op, res : &mut Vec<Wast>) {
             res.push(Wast::Call(EXTERNAL_CALL_READ));
             res.push(Wast::I32Store8);
         },
+        Op::InitTape { data: ref tape, ptr } => {
+            data.push((0, tape.clone()));
+            res.push(Wast::I32Const(ptr as i32));
+            res.push(Wast::SetLocal(0));
+        },
+        Op::PrintBytes(ref bytes) => {
+            let offset = data.iter()
+                .map(|(offset, b)| offset + b.len() as u32)
+                .fold(HEAP_SIZE as u32, ::std::cmp::max);
+            data.push((offset, bytes.clone()));
+            res.push(Wast::I32Const(offset as i32));
+            res.push(Wast::I32Const(bytes.len() as i32));
+            res.push(Wast::Call(EXTERNAL_CALL_WRITE));
+        },
         Op::While {ref ops } => {
             res.push(Wast::Block);
             res.push(Wast::Loop);
@@ -396,11 +558,14 @@ fn to_wasmt (ops: &Op, res : &mut Vec<Wast>) {
             res.push(Wast::I32Load8u);
             res.push(Wast::I32Eqz);
             res.push(Wast::BrIf(1));
+            cover(coverage, Point::LoopBody(node.span), res);
 
             for o in ops {
-                to_wasmt(o, res);
+                to_wasmt(o, res, data, coverage);
             }
 
+            res.push(Wast::Mark(node.span));
+            cover(coverage, Point::Op((node.span.1 - 1, node.span.1)), res);
             res.push(Wast::Br(0));
 
             res.push(Wast::End);
@@ -410,26 +575,79 @@ fn to_wasmt (ops: &Op, res : &mut Vec<Wast>) {
 }
 
 
-pub fn to_wasm (ops: &[Op]) -> Vec<u8> {
+/// `source`, when given, is embedded verbatim in a `brainfuck.source`
+/// custom section.
+fn compile (ops: &[Node], passes: &PassManager, source: Option<&str>, map_url: Option<&str>, coverage: bool) -> (Vec<u8>, Vec<(usize, Span)>, Vec<Point>) {
     let mut wast = vec![];
-    
+    let mut data = vec![];
+    let mut coverage = if coverage {
+        Some(Coverage { base: data_end(ops) as u32, points: vec![] })
+    } else {
+        None
+    };
+
     for op in ops {
-        to_wasmt(op, &mut wast);
+        to_wasmt(op, &mut wast, &mut data, &mut coverage);
     }
 
-    let wast = simple_optimasation(&wast);
+    let wast = if passes.is_enabled(Pass::Peephole) {
+        simple_optimasation(&wast)
+    } else {
+        wast
+    };
+
+    let mut custom = vec![];
+    if let Some(source) = source {
+        custom.push(("brainfuck.source".to_owned(), source.as_bytes().to_vec()));
+    }
+    if let Some(map_url) = map_url {
+        let mut url = leb128::encode_unsigned(map_url.len() as u32);
+        url.extend_from_slice(map_url.as_bytes());
+        custom.push(("sourceMappingURL".to_owned(), url));
+    }
+    if let Some(ref coverage) = coverage {
+        let mut layout = vec![];
+        layout.write_u32::<LittleEndian>(coverage.base).unwrap();
+        layout.write_u32::<LittleEndian>(coverage.points.len() as u32).unwrap();
+        custom.push(("brainfuck.coverage".to_owned(), layout));
+    }
 
     let module = Module{
+        name : "brainfuck".to_owned(),
          imports : vec![("io".to_owned(), "print".to_owned(), TypeDef{ result : false, params : 1 }),
-                        ("io".to_owned(), "read".to_owned(), TypeDef{ result : true, params : 0 })],
+                        ("io".to_owned(), "read".to_owned(), TypeDef{ result : true, params : 0 }),
+                        ("io".to_owned(), "write".to_owned(), TypeDef{ result : false, params : 2 })],
         functions : vec![("exec".to_owned(), TypeDef{
             result : false,
             params : 0
-        }, 1, wast)],
+        }, vec!["ptr".to_owned()], wast)],
+        data,
+        custom,
     };
 
     let mut module_bin = vec![];
-    module.to_binary(&mut module_bin);
-    module_bin
-    
-}
\ No newline at end of file
+    let marks = module.to_binary(&mut module_bin);
+    (module_bin, marks, coverage.map_or_else(Vec::new, |c| c.points))
+}
+
+/// `source`, when given, is embedded verbatim in a `brainfuck.source`
+/// custom section.
+pub fn to_wasm (ops: &[Node], passes: &PassManager, source: Option<&str>) -> Vec<u8> {
+    compile(ops, passes, source, None, false).0
+}
+
+/// Returns the module together with a source map from its code offsets
+/// back to `source`, which the module refers to as `map_url`.
+pub fn to_wasm_with_source_map (ops: &[Node], passes: &PassManager, source: &str, map_url: &str) -> (Vec<u8>, String) {
+    let (module_bin, marks, _) = compile(ops, passes, None, Some(map_url), false);
+    (module_bin, source_map::source_map(source, "program.bf", &marks))
+}
+
+/// An instrumented module that sets one byte of memory to 1 when each of
+/// the returned points first runs. The bytes start at the offset in the
+/// first u32 of its `brainfuck.coverage` custom section, the second u32 is
+/// their number.
+pub fn to_wasm_with_coverage (ops: &[Node], passes: &PassManager) -> (Vec<u8>, Vec<Point>) {
+    let (module_bin, _, points) = compile(ops, passes, None, None, true);
+    (module_bin, points)
+}
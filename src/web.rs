@@ -0,0 +1,164 @@
+// Exports for hosts without emscripten, such as a page loading the
+// wasm32-unknown-unknown build directly.
+//
+// Strings and byte slices go in as a pointer and a length, usually into a
+// buffer from `alloc_bytes`, and must be UTF-8 where text is expected.
+// Every export returns a `JsResult` the caller frees with `drop_result`:
+// `ok` is 1 with the value in `value`, or 0 with an error message there.
+
+use brainfuck::{check_brackets, check_nesting, format, get_ast, Node, Op};
+use json;
+use passes::PassManager;
+use run_result::{self, RunResult};
+use session;
+use {c, js, jvm, llvm, rust, wasm};
+use {bytes, vm_program, JsBytes, JsIo};
+
+#[repr(C)]
+pub struct JsResult {
+    ok: u32,
+    value: JsBytes,
+}
+
+impl JsResult {
+    fn new(result: Result<Vec<u8>, String>) -> *mut JsResult {
+        let (ok, value) = match result {
+            Ok(value) => (1, value),
+            Err(e) => (0, e.into_bytes()),
+        };
+        Box::into_raw(Box::new(JsResult { ok, value: JsBytes::from_vec(value) }))
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must come from one of the `web_` exports and must not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe fn drop_result(ptr: *mut JsResult) {
+    Box::from_raw(ptr).value.into_vec();
+}
+
+unsafe fn text(ptr: *const u8, len: usize, what: &str) -> Result<String, String> {
+    String::from_utf8(bytes(ptr, len).to_vec()).map_err(|_| format!("the {} is not valid UTF-8", what))
+}
+
+// Parses the program and its flags, rejecting unbalanced brackets.
+unsafe fn program(code: *const u8, code_len: usize, flags: *const u8, flags_len: usize)
+                  -> Result<(Vec<char>, PassManager), String> {
+    let code: Vec<char> = text(code, code_len, "program")?.chars().collect();
+    let passes = PassManager::from_flags(&text(flags, flags_len, "flags")?)?;
+    check_brackets(&code)?;
+    Ok((code, passes))
+}
+
+/// The program compiled for `target`: `wasm`, `js`, `c`, `rust`, `llvm` or
+/// `class`.
+///
+/// # Safety
+///
+/// Each pointer must point to its length of readable bytes.
+#[no_mangle]
+pub unsafe fn web_compile(code: *const u8, code_len: usize, flags: *const u8, flags_len: usize,
+                          target: *const u8, target_len: usize) -> *mut JsResult {
+    JsResult::new(program(code, code_len, flags, flags_len).and_then(|(code, passes)| {
+        check_nesting(&code)?;
+        let (ast, _) = passes.run(&get_ast(&code).0);
+        match &text(target, target_len, "target")?[..] {
+            "wasm" => Ok(wasm::to_wasm(&ast, &passes, None)),
+            "js" => Ok(js::to_js(&ast).into_bytes()),
+            "c" => Ok(c::to_c(&ast, &c::Options::default()).into_bytes()),
+            "rust" => Ok(rust::to_rust(&ast).into_bytes()),
+            "llvm" => Ok(llvm::to_llvm(&ast).into_bytes()),
+            "class" => jvm::to_class(&ast, "Main"),
+            target => Err(format!("unknown target '{}'", target)),
+        }
+    }))
+}
+
+fn fuel(max_steps: u32) -> Option<u64> {
+    if max_steps == 0 { None } else { Some(u64::from(max_steps)) }
+}
+
+/// Runs the optimised program, asking the host for input through
+/// `read_val`, and returns the `RunResult` as JSON. `max_steps` of 0 means
+/// no limit.
+///
+/// # Safety
+///
+/// Each pointer must point to its length of readable bytes.
+#[no_mangle]
+pub unsafe fn web_run(code: *const u8, code_len: usize, flags: *const u8, flags_len: usize,
+                      max_steps: u32) -> *mut JsResult {
+    JsResult::new(program(code, code_len, flags, flags_len).map(|(code, passes)| {
+        let mut io = JsIo { pending: Vec::new() };
+        run_result::run(&vm_program(&code, &passes), &mut io, fuel(max_steps)).to_json().into_bytes()
+    }))
+}
+
+/// Like `web_run`, but reads `input` instead, with 0 past its end.
+///
+/// # Safety
+///
+/// Each pointer must point to its length of readable bytes.
+#[no_mangle]
+pub unsafe fn web_run_with_input(code: *const u8, code_len: usize, flags: *const u8, flags_len: usize,
+                                 input: *const u8, input_len: usize, max_steps: u32) -> *mut JsResult {
+    let result = match program(code, code_len, flags, flags_len) {
+        Ok((code, passes)) => session::run_with_input(vm_program(&code, &passes), bytes(input, input_len), fuel(max_steps)),
+        Err(e) => RunResult::error(e),
+    };
+    JsResult::new(Ok(result.to_json().into_bytes()))
+}
+
+/// The program laid out by `brainfuck::format`.
+///
+/// # Safety
+///
+/// `code` must point to `code_len` readable bytes.
+#[no_mangle]
+pub unsafe fn web_format(code: *const u8, code_len: usize) -> *mut JsResult {
+    JsResult::new(text(code, code_len, "program").and_then(|code| {
+        format(&code.chars().collect::<Vec<_>>()).map(String::into_bytes)
+    }))
+}
+
+/// What the program is made of, as JSON: the number of commands, loops and
+/// the deepest nesting, then one entry per optimisation pass with the ops
+/// and loops before and after it.
+///
+/// # Safety
+///
+/// Each pointer must point to its length of readable bytes.
+#[no_mangle]
+pub unsafe fn web_analyse(code: *const u8, code_len: usize, flags: *const u8, flags_len: usize) -> *mut JsResult {
+    JsResult::new(program(code, code_len, flags, flags_len).map(|(code, passes)| {
+        let (ast, _) = get_ast(&code);
+        let (_, stats) = passes.run(&ast);
+        let passes: Vec<String> = stats.iter()
+            .map(|s| format!("{{\"pass\":{},\"ops_before\":{},\"ops_after\":{},\"loops_before\":{},\"loops_after\":{}}}",
+                             json::string(s.pass.name()), s.ops_before, s.ops_after, s.loops_before, s.loops_after))
+            .collect();
+        let commands = code.iter().filter(|&&c| "+-<>.,[]".contains(c)).count();
+        let (loops, depth) = loops(&ast);
+        format!("{{\"commands\":{},\"loops\":{},\"max_depth\":{},\"passes\":[{}]}}",
+                commands, loops, depth, passes.join(",")).into_bytes()
+    }))
+}
+
+// Returns the number of loops and how deeply they nest.
+fn loops(ast: &[Node]) -> (usize, usize) {
+    let mut count = 0;
+    let mut depth = 0;
+    let mut lists = vec![(ast, 0)];
+    while let Some((list, d)) = lists.pop() {
+        depth = depth.max(d);
+        for node in list {
+            if let Op::While { ops: ref body } = node.op {
+                count += 1;
+                lists.push((body, d + 1));
+            }
+        }
+    }
+    (count, depth)
+}
@@ -0,0 +1,178 @@
+// x86-64 machine code for the `Op` tree. The pointer into the tape lives
+// in r12, which is callee-saved and survives both syscalls and calls. The
+// tape starts at rbx and ends at r14, and the pointer wraps around at both
+// ends.
+use brainfuck::*;
+use byteorder::{WriteBytesExt, LittleEndian};
+
+/// How the generated code does I/O.
+pub enum Runtime {
+    /// Linux `read`/`write` syscalls on stdin/stdout.
+    Syscalls { tape_size: u32 },
+    /// `extern "C"` functions called with the context in r13 as their
+    /// first argument: `print(ctx, val)`, `read(ctx) -> val` and
+    /// `write(ctx, ptr, len)`. Only the JIT uses these.
+    #[cfg_attr(not(all(target_arch = "x86_64", target_os = "linux")), allow(dead_code))]
+    Callbacks { print: u64, read: u64, write: u64, tape_size: u32 },
+}
+
+fn emit_u32(code: &mut Vec<u8>, val: u32) {
+    code.write_u32::<LittleEndian>(val).unwrap();
+}
+
+// Writes the rel32 of the jump whose displacement ends at `at` so that it
+// lands on `target`.
+fn patch_rel32(code: &mut [u8], at: usize, target: usize) {
+    let rel = target as i64 - at as i64;
+    (&mut code[at - 4..at]).write_i32::<LittleEndian>(rel as i32).unwrap();
+}
+
+pub fn mov_r12_imm64(code: &mut Vec<u8>, val: u64) {
+    code.extend_from_slice(&[0x49, 0xbc]);
+    code.write_u64::<LittleEndian>(val).unwrap();
+}
+
+// write(1, rsi, edx) / read(0, rsi, edx)
+fn syscall_io(code: &mut Vec<u8>, syscall: u32, fd: u32) {
+    code.push(0xb8); // mov eax, imm32
+    emit_u32(code, syscall);
+    code.push(0xbf); // mov edi, imm32
+    emit_u32(code, fd);
+    code.extend_from_slice(&[0x0f, 0x05]); // syscall
+}
+
+fn mov_rdi_r13(code: &mut Vec<u8>) {
+    code.extend_from_slice(&[0x4c, 0x89, 0xef]);
+}
+
+fn call(code: &mut Vec<u8>, address: u64) {
+    code.extend_from_slice(&[0x48, 0xb8]); // mov rax, imm64
+    code.write_u64::<LittleEndian>(address).unwrap();
+    code.extend_from_slice(&[0xff, 0xd0]); // call rax
+}
+
+impl Runtime {
+    fn tape_size(&self) -> u32 {
+        match *self {
+            Runtime::Syscalls { tape_size } | Runtime::Callbacks { tape_size, .. } => tape_size,
+        }
+    }
+}
+
+/// Sets rbx and r14 to the start and end of the tape at `r12`.
+pub fn tape_bounds(code: &mut Vec<u8>, tape_size: u32) {
+    code.extend_from_slice(&[0x4c, 0x89, 0xe3]); // mov rbx, r12
+    code.extend_from_slice(&[0x4d, 0x89, 0xe6]); // mov r14, r12
+    code.extend_from_slice(&[0x49, 0x81, 0xc6]); // add r14, imm32
+    emit_u32(code, tape_size);
+}
+
+fn wrap_pointer(code: &mut Vec<u8>, runtime: &Runtime) {
+    code.extend_from_slice(&[0x4d, 0x39, 0xf4]); // cmp r12, r14
+    code.extend_from_slice(&[0x72, 0x07]); // jb +7
+    code.extend_from_slice(&[0x49, 0x81, 0xec]); // sub r12, imm32
+    emit_u32(code, runtime.tape_size());
+    code.extend_from_slice(&[0x49, 0x39, 0xdc]); // cmp r12, rbx
+    code.extend_from_slice(&[0x73, 0x07]); // jae +7
+    code.extend_from_slice(&[0x49, 0x81, 0xc4]); // add r12, imm32
+    emit_u32(code, runtime.tape_size());
+}
+
+// Moves by less than the tape size so a single wrap around is enough.
+fn pointer_step(n: usize, runtime: &Runtime) -> u32 {
+    (n % runtime.tape_size() as usize) as u32
+}
+
+pub fn exit(code: &mut Vec<u8>) {
+    code.push(0xb8); // mov eax, 60
+    emit_u32(code, 60);
+    code.extend_from_slice(&[0x31, 0xff]); // xor edi, edi
+    code.extend_from_slice(&[0x0f, 0x05]); // syscall
+}
+
+pub fn compile(ops: &[Node], runtime: &Runtime, code: &mut Vec<u8>) {
+    for node in ops {
+        match node.op {
+            Op::IncPointer(n) => {
+                code.extend_from_slice(&[0x49, 0x81, 0xc4]); // add r12, imm32
+                emit_u32(code, pointer_step(n, runtime));
+                wrap_pointer(code, runtime);
+            }
+            Op::DecPointer(n) => {
+                code.extend_from_slice(&[0x49, 0x81, 0xec]); // sub r12, imm32
+                emit_u32(code, pointer_step(n, runtime));
+                wrap_pointer(code, runtime);
+            }
+            Op::IncVal(n) => code.extend_from_slice(&[0x41, 0x80, 0x04, 0x24, n]), // add byte [r12], imm8
+            Op::DecVal(n) => code.extend_from_slice(&[0x41, 0x80, 0x2c, 0x24, n]), // sub byte [r12], imm8
+            Op::SetRegisterToZero => code.extend_from_slice(&[0x41, 0xc6, 0x04, 0x24, 0]), // mov byte [r12], 0
+            Op::Print => match *runtime {
+                Runtime::Syscalls { .. } => {
+                    code.extend_from_slice(&[0x4c, 0x89, 0xe6]); // mov rsi, r12
+                    code.push(0xba); // mov edx, 1
+                    emit_u32(code, 1);
+                    syscall_io(code, 1, 1);
+                }
+                Runtime::Callbacks { print, .. } => {
+                    mov_rdi_r13(code);
+                    code.extend_from_slice(&[0x41, 0x0f, 0xb6, 0x34, 0x24]); // movzx esi, byte [r12]
+                    call(code, print);
+                }
+            },
+            Op::Read => match *runtime {
+                Runtime::Syscalls { .. } => {
+                    // the cell stays zero when the input is exhausted
+                    code.extend_from_slice(&[0x41, 0xc6, 0x04, 0x24, 0]);
+                    code.extend_from_slice(&[0x4c, 0x89, 0xe6]);
+                    code.push(0xba);
+                    emit_u32(code, 1);
+                    syscall_io(code, 0, 0);
+                }
+                Runtime::Callbacks { read, .. } => {
+                    mov_rdi_r13(code);
+                    call(code, read);
+                    code.extend_from_slice(&[0x41, 0x88, 0x04, 0x24]); // mov byte [r12], al
+                }
+            },
+            Op::InitTape { ref data, ptr } => {
+                for (i, v) in data.iter().enumerate().filter(|&(_, v)| *v != 0) {
+                    code.extend_from_slice(&[0x41, 0xc6, 0x84, 0x24]); // mov byte [r12 + disp32], imm8
+                    emit_u32(code, i as u32);
+                    code.push(*v);
+                }
+                code.extend_from_slice(&[0x49, 0x81, 0xc4]);
+                emit_u32(code, ptr as u32);
+            }
+            Op::PrintBytes(ref bytes) => {
+                // the bytes are stored inline and jumped over
+                code.extend_from_slice(&[0x48, 0x8d, 0x35]); // lea rsi, [rip + 5]
+                emit_u32(code, 5);
+                code.push(0xe9); // jmp rel32
+                emit_u32(code, bytes.len() as u32);
+                code.extend_from_slice(bytes);
+                code.push(0xba);
+                emit_u32(code, bytes.len() as u32);
+                match *runtime {
+                    Runtime::Syscalls { .. } => syscall_io(code, 1, 1),
+                    Runtime::Callbacks { write, .. } => {
+                        mov_rdi_r13(code);
+                        call(code, write);
+                    }
+                }
+            }
+            Op::While { ref ops } => {
+                let start = code.len();
+                code.extend_from_slice(&[0x41, 0x80, 0x3c, 0x24, 0]); // cmp byte [r12], 0
+                code.extend_from_slice(&[0x0f, 0x84]); // je rel32
+                emit_u32(code, 0);
+                let exit_jump = code.len();
+                compile(ops, runtime, code);
+                code.push(0xe9); // jmp rel32
+                emit_u32(code, 0);
+                let end = code.len();
+                patch_rel32(code, end, start);
+                patch_rel32(code, exit_jump, end);
+            }
+        }
+    }
+}